@@ -23,7 +23,7 @@ fn sudoku(c: &mut Criterion) {
             //println!("Input:");
             //println!("{}", Sudoku::pretty(&sudoku));
             //println!();
-            let s = Sudoku::new_from_input(&sudoku);
+            let s = Sudoku::new_from_input(&sudoku).unwrap();
             for _solution in s {
                 //println!("Solution:");
                 //println!("{}", Sudoku::pretty(&solution));
@@ -33,6 +33,77 @@ fn sudoku(c: &mut Criterion) {
     group.finish()
 }
 
+fn sudoku16_setup(c: &mut Criterion) {
+    // A fully-given 16x16 grid (one valid completion, cyclically shifted
+    // rows) exercises select()'s givens setup at its densest: 256 select()
+    // calls against a ~4096-option solver, which is exactly the case
+    // spacer_by_index caching targets
+    let mut sudoku16 = vec![0usize; 256];
+    for row in 0..16 {
+        for col in 0..16 {
+            // Knuth's base-pattern generator for a valid NxN sudoku solution
+            sudoku16[row * 16 + col] = (4 * (row % 4) + row / 4 + col) % 16 + 1;
+        }
+    }
+
+    let mut group = c.benchmark_group("sample-size");
+    group
+        .sample_size(100)
+        .measurement_time(Duration::from_secs(30));
+
+    group.bench_function("sudoku16_setup", |b| {
+        b.iter(|| {
+            let _s = Sudoku::new_from_input(&sudoku16).unwrap();
+        })
+    });
+    group.finish()
+}
+
+fn eight_queens_solver() -> Solver {
+    // Mirrors dlx_rs::queens::Queens::new(8)'s constraint layout: Queens
+    // keeps its Solver private, so count_solutions (a Solver method) is
+    // benchmarked against a hand-built 8-queens instance instead -- 92
+    // solutions is enough search volume for output()'s per-solution
+    // allocation to actually show up against count_solutions()'s allocation-free
+    // win check
+    let n = 8;
+    let mandatory = 2 * n;
+    let optional = n * n + 6 * n - 2;
+    let mut solver: Solver = Solver::new_optional(mandatory, optional);
+
+    for r in 1..=n {
+        for c in 1..=n {
+            let con_name = format!("R{}C{}", r, c);
+            let col_con = c;
+            let row_con = n + r;
+            let rd_con = 2 * n + c - r + n;
+            let ld_con = 4 * n - 1 + r + c - 1;
+            let is_queen = 6 * n - 2 + r + n * (c - 1);
+
+            solver.add_option(&con_name, &[col_con, row_con, rd_con, ld_con, is_queen]);
+        }
+    }
+
+    solver
+}
+
+fn count_vs_count_solutions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sample-size");
+    group
+        .sample_size(100)
+        .measurement_time(Duration::from_secs(30));
+
+    group.bench_function("count_iterator", |b| {
+        b.iter(|| eight_queens_solver().count())
+    });
+
+    group.bench_function("count_solutions", |b| {
+        b.iter(|| eight_queens_solver().count_solutions())
+    });
+
+    group.finish()
+}
+
 fn simple(c: &mut Criterion) {
     let mut group = c.benchmark_group("sample-size");
     group
@@ -41,7 +112,7 @@ fn simple(c: &mut Criterion) {
 
     group.bench_function("simple", |b| {
         b.iter(|| {
-            let mut s = Solver::new(7);
+            let mut s: Solver = Solver::new(7);
 
             s.add_option("o1", &[3, 5])
                 .add_option("o2", &[1, 4, 7])
@@ -56,5 +127,5 @@ fn simple(c: &mut Criterion) {
     group.finish()
 }
 
-criterion_group!(benches, sudoku, simple);
+criterion_group!(benches, sudoku, sudoku16_setup, count_vs_count_solutions, simple);
 criterion_main!(benches);
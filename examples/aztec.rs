@@ -1,22 +1,21 @@
 use dlx_rs::aztec::Aztec;
-use rand::seq::IteratorRandom;
 
 // Solve the Aztec diamond of order n
 
 fn main() {
     for n in 1..=4 {
-        //let n = 4;
-
         // First, count all of the solutions
         let a = Aztec::new(n);
         let na = a.count();
         println!("Number of solutions for n={}: {}", n, na);
-
-        // Get a random solution
-        let a = Aztec::new(n);
-        let mut rng = rand::thread_rng();
-        let s = a.choose(&mut rng).unwrap();
-
-        Aztec::pretty_print_sol(&s);
     }
+
+    // random_tiling picks a uniformly random tiling via reservoir sampling
+    // over every tiling, so it holds only one tiling in memory at a time --
+    // but it still visits all 2^(n*(n+1)/2) of them to do so, so n has to
+    // stay moderate; this is not the scalable domino-shuffling sampler a
+    // serious arctic-circle visualization would eventually need
+    let mut rng = rand::thread_rng();
+    let s = Aztec::random_tiling(5, &mut rng);
+    Aztec::pretty_print_sol(&s);
 }
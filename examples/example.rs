@@ -13,7 +13,7 @@ use dlx_rs::solver::Solver;
 // The only valid solution is [B,D,F]
 
 fn main() {
-    let mut s = Solver::new(7);
+    let mut s: Solver = Solver::new(7);
 
     s.add_option("A", &[1, 4, 7])
         .add_option("B", &[1, 4])
@@ -0,0 +1,49 @@
+use dlx_rs::solver::{Solver, StepOutcome};
+
+// Drives the dancing-links state machine one step at a time instead of
+// running it to completion, printing the matrix and the decode helpers
+// after every step -- the kind of loop an interactive terminal UI would
+// build on top of `step`, `current_partial`, `uncovered_items` and `to_dot`.
+//
+//     i1  i2  i3  i4  i5  i6  i7
+// A   x           x           x
+// B   x           x
+// C               x   x       x
+// D           x       x   x
+// E       x   x           x   x
+// F       x                   x
+//
+// The only valid solution is [B,D,F]
+
+fn main() {
+    let mut s: Solver = Solver::new(7);
+
+    s.add_option("A", &[1, 4, 7])
+        .add_option("B", &[1, 4])
+        .add_option("C", &[4, 5, 7])
+        .add_option("D", &[3, 5, 6])
+        .add_option("E", &[2, 3, 6, 7])
+        .add_option("F", &[2, 7]);
+
+    let mut step_no = 0;
+    loop {
+        step_no += 1;
+        match s.step() {
+            StepOutcome::Continue => {
+                println!("--- step {step_no} ---");
+                println!("uncovered items: {:?}", s.uncovered_items());
+                println!("partial solution: {:?}", s.current_partial());
+                println!("{}", s);
+            }
+            StepOutcome::Solution(sol) => {
+                println!("--- step {step_no}: solution ---");
+                println!("{:?}", sol);
+                println!("dot graph of remaining state:\n{}", s.to_dot());
+            }
+            StepOutcome::Exhausted => {
+                println!("--- step {step_no}: exhausted ---");
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use dlx_rs::nonogram::Nonogram;
+
+// Solve a small Nonogram (Picross):
+//
+//  #..#
+//  ####
+//  .##.
+//
+// Row clues:    [1,1], [4], [2]
+// Column clues: [2], [2], [2], [2]
+
+fn main() {
+    let row_clues = vec![vec![1, 1], vec![4], vec![2]];
+    let col_clues = vec![vec![2], vec![2], vec![2], vec![2]];
+
+    let mut nonogram = Nonogram::new(&row_clues, &col_clues);
+    match nonogram.next() {
+        Some(grid) => {
+            for row in &grid {
+                let line: String = row.iter().map(|&filled| if filled { '#' } else { '.' }).collect();
+                println!("{line}");
+            }
+        }
+        None => println!("no grid satisfies both the row and column clues"),
+    }
+}
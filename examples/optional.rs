@@ -16,7 +16,7 @@ use dlx_rs::solver::Solver;
 // [o7, o4, o5] (i8 now covered)
 
 fn main() {
-    let mut s = Solver::new_optional(7, 1);
+    let mut s: Solver = Solver::new_optional(7, 1);
 
     s.add_option("o1", &[3, 5])
         .add_option("o2", &[1, 4, 7])
@@ -13,7 +13,7 @@ use dlx_rs::solver::Solver;
 // The only valid solution is [o1,o4,o5]
 
 fn main() {
-    let mut s = Solver::new(7);
+    let mut s: Solver = Solver::new(7);
 
     s.add_option("o1", &[3, 5])
         .add_option("o2", &[1, 4, 7])
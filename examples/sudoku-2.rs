@@ -24,7 +24,7 @@ fn main() {
     println!("Input:");
     println!("{}", Sudoku::pretty(&sudoku));
     println!();
-    let s = Sudoku::new_from_input(&sudoku);
+    let s = Sudoku::new_from_input(&sudoku).unwrap();
     for solution in s {
         println!("Solution:");
         println!("{}", Sudoku::pretty(&solution));
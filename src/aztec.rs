@@ -1,6 +1,8 @@
 use crate::Solver;
+use rand::Rng;
 use std::collections::HashSet;
 
+#[derive(Clone, Copy)]
 enum Color {
     Red,
     Yellow,
@@ -9,6 +11,71 @@ enum Color {
     Black,
 }
 
+/// Output format for [Aztec::render]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderStyle {
+    /// ANSI background-colour escape codes, for a terminal
+    Ansi,
+    /// Plain-text letters naming the orientation/colour class, for logs
+    Ascii,
+    /// `<span>` elements with inline CSS background colours, for a web page
+    Html,
+}
+
+/// One of the four domino types in the arctic-circle analysis of a domino
+/// tiling, as classified by [domino_color_class]
+///
+/// Every domino in an Aztec diamond tiling is either horizontal or
+/// vertical, and either "even" or "odd" relative to the diamond's centre;
+/// those two bits give the four classes, conventionally drawn in four
+/// colours when rendering a tiling (see [Aztec::render]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorClass {
+    /// Vertical domino, odd parity
+    Red,
+    /// Vertical domino, even parity
+    Green,
+    /// Horizontal domino, odd parity
+    Yellow,
+    /// Horizontal domino, even parity
+    Blue,
+}
+
+/// Classifies a domino spanning squares `pos1` and `pos2` of the order-`n`
+/// Aztec diamond into its [ColorClass]
+///
+/// This is the mathematically meaningful part of [Aztec::render]'s
+/// colouring -- the four domino types studied in the arctic-circle
+/// analysis of random tilings -- pulled out as a pure function so callers
+/// can classify dominoes in their own tilings without going through the
+/// ANSI/ASCII/HTML rendering machinery at all. `pos1` and `pos2` are the
+/// domino's two square positions in [Aztec]'s row-major numbering, in
+/// either order.
+/// ```
+///# use dlx_rs::aztec::{domino_color_class, ColorClass};
+/// // The order-2 diamond's first tiling pairs squares 1 and 2 into a
+/// // horizontal domino classified Yellow
+/// assert_eq!(domino_color_class(1, 2, 2), ColorClass::Yellow);
+/// ```
+pub fn domino_color_class(pos1: usize, pos2: usize, n: usize) -> ColorClass {
+    let min = pos1.min(pos2);
+    let max = pos1.max(pos2);
+
+    let par = if min > n * (n + 1) { 1 } else { 0 };
+
+    if max == min + 1 {
+        if min % 2 == par {
+            ColorClass::Blue
+        } else {
+            ColorClass::Yellow
+        }
+    } else if min % 2 == par {
+        ColorClass::Green
+    } else {
+        ColorClass::Red
+    }
+}
+
 /// Finds all solutions to Aztec diamond problem
 /// ```
 ///# use dlx_rs::aztec::Aztec;
@@ -46,7 +113,7 @@ impl Aztec {
         //      19 20 21 22
         //         23 24
 
-        let mut solver = Solver::new(2 * n * (n + 1));
+        let mut solver: Solver = Solver::new(2 * n * (n + 1));
 
         // Now add options: each option corresponds to either a vertical or horizontal dominos
         // We first add the horizontal dominos, which run along every tile except for the last on each row, which means we have
@@ -105,74 +172,127 @@ impl Aztec {
         Aztec { solver, n }
     }
 
-    /// Prints a solution using ANSI colour codes on the terminal
+    /// Draws a uniformly random tiling of the order `n` Aztec diamond,
+    /// i.e. each of the `2^(n*(n+1)/2)` domino tilings is equally likely
+    ///
+    /// This uses [reservoir sampling](https://en.wikipedia.org/wiki/Reservoir_sampling)
+    /// over every tiling in turn, so unlike collecting all of them into a
+    /// `Vec` and calling `.choose()` it only ever holds one tiling in memory
+    /// at a time -- but it still visits every one of the `2^(n*(n+1)/2)`
+    /// tilings to do so, the same exponential cost as `.choose()`. That
+    /// makes this suitable for moderate `n` (enough to see the arctic
+    /// circle phenomenon start to emerge) but **not** a fix for the
+    /// underlying scalability problem; the real fix is an algorithm like
+    /// domino shuffling that samples a tiling directly without enumerating
+    /// the rest, which this does not implement.
+    ///
     /// ```
     ///# use dlx_rs::aztec::Aztec;
-    /// let n = 5;
-    /// let az = Aztec::new(n);
+    /// let mut rng = rand::thread_rng();
+    /// let tiling = Aztec::random_tiling(4, &mut rng);
+    /// assert_eq!(tiling.len(), 4 * 5);
+    /// ```
+    pub fn random_tiling(n: usize, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+        let mut chosen = None;
+        for (seen, sol) in Aztec::new(n).enumerate() {
+            if rng.gen_range(0..=seen) == 0 {
+                chosen = Some(sol);
+            }
+        }
+        chosen.expect("the order n Aztec diamond always has at least one tiling")
+    }
+
+    /// Parses solution option names of the form `H{pos1}#{pos2}` /
+    /// `V{pos1}#{pos2}` into the domino endpoints they describe
+    fn parse_solution(sol: &[String]) -> Vec<(usize, usize)> {
+        sol.iter()
+            .map(|i| {
+                let s: Vec<&str> = i.split(&['H', 'V', '#']).collect();
+                let p1: usize = s[1].parse().unwrap();
+                let p2: usize = s[2].parse().unwrap();
+                (p1, p2)
+            })
+            .collect()
+    }
+
+    /// Classifies every square of the order-`n` diamond tiling `sol` into
+    /// one of the five colour classes shared by every [RenderStyle] --
+    /// the part of rendering that doesn't depend on the output format
+    fn color_classes(sol: &[(usize, usize)], n: usize) -> Vec<Color> {
+        let mut solc: Vec<Color> = vec![Color::Black; 2 * sol.len()];
+
+        for &(pos1, pos2) in sol {
+            let min = pos1.min(pos2);
+            let max = pos1.max(pos2);
+
+            let color = match domino_color_class(pos1, pos2, n) {
+                ColorClass::Red => Color::Red,
+                ColorClass::Green => Color::Green,
+                ColorClass::Yellow => Color::Yellow,
+                ColorClass::Blue => Color::Blue,
+            };
+            solc[min - 1] = color;
+            solc[max - 1] = color;
+        }
+
+        solc
+    }
+
+    /// Renders an order-`n` diamond tiling `sol` to a `String` in the
+    /// chosen [RenderStyle]
+    ///
+    /// The colour classification is computed once by [color_classes] and
+    /// shared across every style; only the final byte emission per square
+    /// differs, so terminal, log-file and web callers all walk the same
+    /// diamond layout
+    /// ```
+    ///# use dlx_rs::aztec::{Aztec, RenderStyle};
+    /// let az = Aztec::new(2);
     /// for sol in az {
-    ///     Aztec::pretty_print_sol(&sol);
+    ///     let ascii = Aztec::render(&sol, 2, RenderStyle::Ascii);
+    ///     assert!(ascii.chars().all(|c| "RGYB. \n".contains(c)));
     /// }
     /// ```
-    pub fn pretty_print_sol(sol: &[(usize, usize)]) {
-        // Gets n from length of solution
-        let n = (sol.len() as f64).sqrt() as usize;
+    pub fn render(sol: &[(usize, usize)], n: usize, style: RenderStyle) -> String {
         let max = 2 * n * (n + 1);
-
-        // Construct positions at end of row
         let row_ends_top = (1..=n).map(|x| x * (x + 1));
         let row_ends_bottom = (1..=n).map(|x| max - x * (x - 1));
+        let row_ends_set: HashSet<usize> = row_ends_top.chain(row_ends_bottom).collect();
 
-        let row_ends = row_ends_top.chain(row_ends_bottom);
-        let row_ends_set: HashSet<usize> = HashSet::from_iter(row_ends);
+        let solc = Self::color_classes(sol, n);
 
-        let mut solc: Vec<Color> = Vec::with_capacity(2 * sol.len());
-        for _ in 1..=2 * sol.len() {
-            solc.push(Color::Black);
-        }
-
-        // Go through items in solution
-        for item in sol {
-            let min = (item.0).min(item.1);
-            let max = (item.0).max(item.1);
+        let mut result = String::new();
+        let mut row_dir = true;
+        let mut row_pad = n;
+        result += &" ".repeat(row_pad);
 
-            let par = match min {
-                x if x > n * (n + 1) => 1,
-                _ => 0,
-            };
-            // If horizontal bond
-            if max == min + 1 {
-                if min % 2 == par {
-                    solc[min - 1] = Color::Blue;
-                    solc[max - 1] = Color::Blue;
-                } else {
-                    solc[min - 1] = Color::Yellow;
-                    solc[max - 1] = Color::Yellow;
+        for (i, c) in solc.iter().enumerate() {
+            match style {
+                RenderStyle::Ansi => result += match c {
+                    Color::Red => "\x1b[31;41mX\x1b[0m",
+                    Color::Green => "\x1b[32;42mX\x1b[0m",
+                    Color::Yellow => "\x1b[33;43mX\x1b[0m",
+                    Color::Blue => "\x1b[34;44mX\x1b[0m",
+                    Color::Black => "\x1b[30;40mX\x1b[0m",
+                },
+                RenderStyle::Ascii => result.push(match c {
+                    Color::Red => 'R',
+                    Color::Green => 'G',
+                    Color::Yellow => 'Y',
+                    Color::Blue => 'B',
+                    Color::Black => '.',
+                }),
+                RenderStyle::Html => {
+                    let name = match c {
+                        Color::Red => "red",
+                        Color::Green => "green",
+                        Color::Yellow => "yellow",
+                        Color::Blue => "blue",
+                        Color::Black => "black",
+                    };
+                    result += &format!("<span style=\"background-color:{name}\">&nbsp;</span>");
                 }
-            } else if min % 2 == par {
-                solc[min - 1] = Color::Green;
-                solc[max - 1] = Color::Green;
-            } else {
-                solc[min - 1] = Color::Red;
-                solc[max - 1] = Color::Red;
             }
-        }
-
-        // Now print first n rows
-        let mut row_dir = true;
-        let mut row_pad = n;
-        let mut rr = " ".repeat(row_pad);
-
-        print!("{}", rr);
-        for (i, _) in solc.iter().enumerate() {
-            // Print appropriate colour
-            match solc[i] {
-                Color::Red => print!("\x1b[31;41mX\x1b[0m"),
-                Color::Green => print!("\x1b[32;42mX\x1b[0m"),
-                Color::Yellow => print!("\x1b[33;43mX\x1b[0m"),
-                Color::Blue => print!("\x1b[34;44mX\x1b[0m"),
-                Color::Black => print!("\x1b[30;40mX\x1b[0m"),
-            };
 
             // Padding for each row, decrease padding until row `n`, then increase again
             if row_ends_set.contains(&(i + 1)) {
@@ -185,12 +305,47 @@ impl Aztec {
                 } else {
                     row_pad += 1;
                 }
-                println!();
-                rr = " ".repeat(row_pad);
-                print!("{}", rr);
+                result += if style == RenderStyle::Html { "<br>\n" } else { "\n" };
+                result += &" ".repeat(row_pad);
             }
         }
-        println!();
+        result += if style == RenderStyle::Html { "<br>\n" } else { "\n" };
+
+        result
+    }
+
+    /// Prints a solution using ANSI colour codes on the terminal
+    /// ```
+    ///# use dlx_rs::aztec::Aztec;
+    /// let n = 5;
+    /// let az = Aztec::new(n);
+    /// for sol in az {
+    ///     Aztec::pretty_print_sol(&sol);
+    /// }
+    /// ```
+    pub fn pretty_print_sol(sol: &[(usize, usize)]) {
+        // Gets n from length of solution
+        let n = (sol.len() as f64).sqrt() as usize;
+        print!("{}", Self::render(sol, n, RenderStyle::Ansi));
+    }
+
+    /// Exhausts the search and serializes every remaining tiling as a JSON
+    /// array of domino endpoint coordinate lists
+    ///
+    /// Eager, like [Solver::solutions_json](crate::solver::Solver::solutions_json):
+    /// the full solution set is collected before being serialized.
+    /// ```
+    ///# use dlx_rs::aztec::Aztec;
+    /// let mut az = Aztec::new(1);
+    /// let json = az.solutions_json();
+    /// let tilings: Vec<Vec<(usize, usize)>> = serde_json::from_str(&json).unwrap();
+    /// assert_eq!(tilings.len(), 2);
+    /// assert_eq!(tilings[0].len(), 2);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn solutions_json(&mut self) -> String {
+        let solutions: Vec<Vec<(usize, usize)>> = self.by_ref().collect();
+        serde_json::to_string(&solutions).expect("Vec<Vec<(usize, usize)>> always serializes")
     }
 }
 
@@ -198,18 +353,78 @@ impl Iterator for Aztec {
     type Item = Vec<(usize, usize)>;
     /// Returns the next solution, which is a vector of tuples denoting the Row and Column of the N queens in the solution
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(sol) = self.solver.next() {
-            let mut dom_solved = Vec::with_capacity(self.n);
-            for i in sol {
-                let i = i.as_str();
-                let s: Vec<&str> = i.split(&['H', 'V', '#']).collect();
-                let p1: usize = s[1].parse().unwrap();
-                let p2: usize = s[2].parse().unwrap();
-                dom_solved.push((p1, p2));
-            }
-            Some(dom_solved)
-        } else {
-            None
-        }
+        let sol = self.solver.next()?;
+        debug_assert_eq!(sol.len(), self.n * (self.n + 1));
+        Some(Self::parse_solution(&sol))
     }
 }
+
+/// `next` forwards directly to the underlying [Solver], which is fused
+impl std::iter::FusedIterator for Aztec {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first_order_2_tiling() -> Vec<(usize, usize)> {
+        Aztec::new(2).next().expect("order 2 diamond always has a tiling")
+    }
+
+    #[test]
+    fn render_ansi_order_2() {
+        let sol = first_order_2_tiling();
+        let rendered = Aztec::render(&sol, 2, RenderStyle::Ansi);
+        assert!(rendered.contains("\x1b[33;43mX\x1b[0m"));
+        assert!(rendered.contains("\x1b[34;44mX\x1b[0m"));
+        assert_eq!(rendered.lines().count(), 5);
+    }
+
+    #[test]
+    fn render_ascii_order_2() {
+        let sol = first_order_2_tiling();
+        let rendered = Aztec::render(&sol, 2, RenderStyle::Ascii);
+        assert_eq!(rendered, "  YY\n YYYY\n BBBB\n  BB\n   \n");
+    }
+
+    #[test]
+    fn render_html_order_2() {
+        let sol = first_order_2_tiling();
+        let rendered = Aztec::render(&sol, 2, RenderStyle::Html);
+        assert!(rendered.starts_with("  <span style=\"background-color:yellow\">"));
+        assert!(rendered.ends_with("<br>\n"));
+        assert_eq!(rendered.matches("<span").count(), 12);
+    }
+
+    #[test]
+    fn domino_color_class_matches_known_positions_in_order_2_diamond() {
+        // Real domino positions drawn from the order-2 diamond's own
+        // solutions, one per colour class
+        assert_eq!(domino_color_class(1, 2, 2), ColorClass::Yellow);
+        assert_eq!(domino_color_class(7, 8, 2), ColorClass::Blue);
+        assert_eq!(domino_color_class(5, 9, 2), ColorClass::Red);
+        assert_eq!(domino_color_class(6, 10, 2), ColorClass::Green);
+    }
+
+    #[test]
+    fn domino_color_class_is_order_independent() {
+        assert_eq!(
+            domino_color_class(1, 2, 2),
+            domino_color_class(2, 1, 2)
+        );
+        assert_eq!(
+            domino_color_class(5, 9, 2),
+            domino_color_class(9, 5, 2)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn solutions_json_round_trips_as_coordinates() {
+        let mut az = Aztec::new(2);
+        let json = az.solutions_json();
+        let tilings: Vec<Vec<(usize, usize)>> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(tilings, Aztec::new(2).collect::<Vec<_>>());
+    }
+}
+
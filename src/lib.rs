@@ -8,7 +8,8 @@
 //! * arbitrary Sudokus
 //! * N queens problem
 //! * Aztec diamond
-//! * Pentomino tilings (TODO)
+//! * arbitrary polyomino tilings
+//! * Nonogram (Picross) puzzles
 //! * graph colouring (TODO)
 //!
 //!
@@ -37,7 +38,7 @@
 //! The code to solve this is
 //! ```
 //! use dlx_rs::Solver;
-//! let mut s = Solver::new(7);
+//! let mut s: Solver = Solver::new(7);
 //! s.add_option("o1", &[3, 5])
 //!     .add_option("o2", &[1, 5, 7])
 //!     .add_option("o3", &[2, 3, 6])
@@ -69,7 +70,7 @@
 //! ];
 //!
 //! // Create new sudoku from this grid
-//! let mut s = Sudoku::new_from_input(&sudoku);
+//! let mut s = Sudoku::new_from_input(&sudoku).unwrap();
 //!
 //! let true_solution = vec![
 //!     5, 3, 4, 6, 7, 8, 9, 1, 2,
@@ -91,11 +92,16 @@
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 pub mod aztec;
+mod macros;
+pub mod nonogram;
 pub mod queens;
 pub mod solver;
 pub mod sudoku;
+pub mod tiling;
 
 pub use crate::aztec::Aztec;
+pub use crate::nonogram::Nonogram;
 pub use crate::queens::Queens;
 pub use crate::solver::Solver;
 pub use crate::sudoku::Sudoku;
+pub use crate::tiling::Tiling;
@@ -0,0 +1,46 @@
+/// Declaratively builds a [Solver](crate::solver::Solver), expanding to the
+/// equivalent [Solver::new](crate::solver::Solver::new) (or
+/// [new_optional](crate::solver::Solver::new_optional), when an `optional`
+/// item count is given) followed by a chain of
+/// [add_option](crate::solver::Solver::add_option) calls
+///
+/// ```
+/// use dlx_rs::solver;
+/// let mut s = solver!(items = 7, options = {
+///     "o1": [3, 5],
+///     "o2": [1, 5, 7],
+///     "o3": [2, 3, 6],
+///     "o4": [1, 4, 6],
+///     "o5": [2, 7],
+///     "o6": [4, 5, 7],
+/// });
+/// assert_eq!(s.next().unwrap_or_default(), ["o4", "o5", "o1"]);
+/// ```
+///
+/// An `optional` item count adds the secondary items described in
+/// [new_optional](crate::solver::Solver::new_optional):
+/// ```
+/// use dlx_rs::solver;
+/// let mut s = solver!(items = 1, optional = 1, options = {
+///     "o1": [1],
+///     "o2": [1, 2],
+/// });
+/// assert_eq!(s.count(), 2);
+/// ```
+#[macro_export]
+macro_rules! solver {
+    (items = $items:expr, options = { $($name:literal : [$($item:expr),* $(,)?]),* $(,)? }) => {{
+        let mut s: $crate::solver::Solver = $crate::solver::Solver::new($items);
+        $(
+            s.add_option($name, &[$($item),*]);
+        )*
+        s
+    }};
+    (items = $items:expr, optional = $optional:expr, options = { $($name:literal : [$($item:expr),* $(,)?]),* $(,)? }) => {{
+        let mut s: $crate::solver::Solver = $crate::solver::Solver::new_optional($items, $optional);
+        $(
+            s.add_option($name, &[$($item),*]);
+        )*
+        s
+    }};
+}
@@ -0,0 +1,210 @@
+use crate::Solver;
+
+/// Generates every row pattern of width `width` consistent with `clue`,
+/// the run-lengths of consecutive filled cells in order
+///
+/// Shared between rows and columns: a nonogram line's clue means the same
+/// thing regardless of which axis it runs along.
+fn line_candidates(width: usize, clue: &[usize]) -> Vec<Vec<bool>> {
+    if clue.is_empty() {
+        return vec![vec![false; width]];
+    }
+
+    let run_count = clue.len();
+    let run_total: usize = clue.iter().sum();
+    let min_width = run_total + (run_count - 1);
+    if min_width > width {
+        return Vec::new();
+    }
+    let slack = width - min_width;
+
+    gap_distributions(slack, run_count + 1)
+        .into_iter()
+        .map(|gaps| {
+            let mut pattern = vec![false; width];
+            let mut pos = 0;
+            for (i, &run) in clue.iter().enumerate() {
+                pos += gaps[i];
+                if i > 0 {
+                    pos += 1;
+                }
+                for cell in pattern.iter_mut().skip(pos).take(run) {
+                    *cell = true;
+                }
+                pos += run;
+            }
+            pattern
+        })
+        .collect()
+}
+
+/// Every way to split `total` extra blanks across `parts` gaps (the
+/// leading gap, one between each pair of runs, and the trailing gap)
+fn gap_distributions(total: usize, parts: usize) -> Vec<Vec<usize>> {
+    if parts == 1 {
+        return vec![vec![total]];
+    }
+    (0..=total)
+        .flat_map(|first| {
+            gap_distributions(total - first, parts - 1)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, first);
+                    rest
+                })
+        })
+        .collect()
+}
+
+/// Returns whether `line` (a row or column read off a candidate grid)
+/// has runs of filled cells matching `clue` exactly
+fn line_matches_clue(line: impl Iterator<Item = bool>, clue: &[usize]) -> bool {
+    let mut runs = Vec::new();
+    let mut current = 0;
+    for filled in line {
+        if filled {
+            current += 1;
+        } else if current > 0 {
+            runs.push(current);
+            current = 0;
+        }
+    }
+    if current > 0 {
+        runs.push(current);
+    }
+    runs == clue
+}
+
+/// Solves a Nonogram (also known as Picross or Paint by Numbers): a grid
+/// whose rows and columns are labelled with the run-lengths of their
+/// filled cells, read left-to-right or top-to-bottom
+///
+/// Only the row clues are encoded as exact cover: one mandatory item per
+/// row, and one option per candidate pattern already satisfying that
+/// row's clue, carrying the pattern itself as metadata (the same
+/// `Solver<M>` metadata mechanism [Tiling](crate::tiling::Tiling) uses
+/// for its placements). [Solver::next] then enumerates every combination
+/// of per-row patterns; each candidate grid is checked against the
+/// column clues directly, moving on to the next combination on a
+/// mismatch. This reuses the same `Solver` search used everywhere else
+/// in the crate, rather than needing a dedicated "colored" cross-line
+/// exact cover extension.
+/// ```
+///# use dlx_rs::nonogram::Nonogram;
+/// // #..#
+/// // ####
+/// // .##.
+/// let row_clues = vec![vec![1, 1], vec![4], vec![2]];
+/// let col_clues = vec![vec![2], vec![2], vec![2], vec![2]];
+///
+/// let mut nonogram = Nonogram::new(&row_clues, &col_clues);
+/// let grid = nonogram.next().unwrap();
+/// assert_eq!(
+///     grid,
+///     vec![
+///         vec![true, false, false, true],
+///         vec![true, true, true, true],
+///         vec![false, true, true, false],
+///     ]
+/// );
+/// ```
+pub struct Nonogram {
+    col_clues: Vec<Vec<usize>>,
+    solver: Solver<Vec<bool>>,
+}
+
+impl Nonogram {
+    /// Builds a nonogram from its row and column clues
+    pub fn new(row_clues: &[Vec<usize>], col_clues: &[Vec<usize>]) -> Nonogram {
+        let width = col_clues.len();
+        let mut solver: Solver<Vec<bool>> = Solver::new(row_clues.len());
+
+        for (r, clue) in row_clues.iter().enumerate() {
+            for pattern in line_candidates(width, clue) {
+                let name = format!(
+                    "R{}:{}",
+                    r + 1,
+                    pattern
+                        .iter()
+                        .map(|&filled| if filled { '#' } else { '.' })
+                        .collect::<String>()
+                );
+                solver.add_option_with_meta(&name, &[r + 1], pattern);
+            }
+        }
+
+        Nonogram {
+            col_clues: col_clues.to_vec(),
+            solver,
+        }
+    }
+}
+
+impl Iterator for Nonogram {
+    type Item = Vec<Vec<bool>>;
+
+    /// Returns the next grid consistent with both the row and column
+    /// clues, or `None` once every row-pattern combination has been tried
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.solver.next().is_some() {
+            // Solutions aren't necessarily produced in row order, so pair
+            // each option's single covered item (its row number) with its
+            // pattern and sort by that before reassembling the grid
+            let mut by_row: Vec<(usize, Vec<bool>)> = self
+                .solver
+                .solution_coverage()
+                .into_iter()
+                .zip(self.solver.output_meta())
+                .map(|(row, pattern)| {
+                    (
+                        row,
+                        pattern.cloned().expect("every row option carries its pattern"),
+                    )
+                })
+                .collect();
+            by_row.sort_by_key(|&(row, _)| row);
+            let grid: Vec<Vec<bool>> = by_row.into_iter().map(|(_, pattern)| pattern).collect();
+
+            let columns_match = self.col_clues.iter().enumerate().all(|(c, clue)| {
+                line_matches_clue(grid.iter().map(|row| row[c]), clue)
+            });
+            if columns_match {
+                return Some(grid);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_a_small_known_grid() {
+        let row_clues = vec![vec![1, 1], vec![4], vec![2]];
+        let col_clues = vec![vec![2], vec![2], vec![2], vec![2]];
+
+        let mut nonogram = Nonogram::new(&row_clues, &col_clues);
+        let grid = nonogram.next().unwrap();
+        assert_eq!(
+            grid,
+            vec![
+                vec![true, false, false, true],
+                vec![true, true, true, true],
+                vec![false, true, true, false],
+            ]
+        );
+    }
+
+    #[test]
+    fn returns_none_when_no_grid_satisfies_both_axes() {
+        // Every candidate row leaves at least two columns blank, so no
+        // arrangement can make all four columns show a single filled cell
+        let row_clues = vec![vec![2]];
+        let col_clues = vec![vec![1], vec![1], vec![1], vec![1]];
+
+        let mut nonogram = Nonogram::new(&row_clues, &col_clues);
+        assert_eq!(nonogram.next(), None);
+    }
+}
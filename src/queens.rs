@@ -1,4 +1,5 @@
 use crate::Solver;
+use std::collections::HashMap;
 
 /// Implements solution to the N queens problem
 ///
@@ -31,9 +32,19 @@ impl Queens {
         // 4. Each of the 2*N-1 left diagonals may have at most one queen
         // 5. Each of the N^2 squares may have at most one queen
 
-        // So this gives us 2*N mandatory items, and N^2 + 6*N -2 optional ones
+        // So this gives us 2*N mandatory items, and N^2 + 6*N -2 optional ones.
+        // Computed with checked arithmetic so a huge n fails loudly here
+        // rather than silently wrapping into a mis-sized solver
+        let mandatory = n
+            .checked_mul(2)
+            .expect("queens board too large: 2*n overflows usize");
+        let optional = n
+            .checked_mul(n)
+            .and_then(|nn| nn.checked_add(n.checked_mul(6)?))
+            .and_then(|v| v.checked_sub(2))
+            .expect("queens board too large: n*n + 6*n - 2 overflows usize");
 
-        let mut solver = Solver::new_optional(2 * n, n * n + 6 * n - 2);
+        let mut solver: Solver = Solver::new_optional(mandatory, optional);
 
         // Now add options: each option corresponds to a queen in a particular
 
@@ -57,6 +68,232 @@ impl Queens {
 
         Queens { solver, n }
     }
+
+    /// Creates a new `Queens` set up with constraints for placing
+    /// `min(m, n)` mutually non-attacking queens on an `m`-row by
+    /// `n`-column board
+    ///
+    /// Generalizes [new](Queens::new)'s square-board constraints to a
+    /// rectangular board: whichever dimension is smaller gets the
+    /// mandatory "exactly one queen" constraint (so every solution places
+    /// that many queens, one per row or one per column), while the larger
+    /// dimension and both diagonal directions become "at most one"
+    /// constraints, just like `new`'s optional items.
+    /// ```
+    ///# use dlx_rs::queens::Queens;
+    /// // 2 rows, 3 columns: the only non-attacking placements put the
+    /// // queens in columns 1 and 3
+    /// let q = Queens::new_rect(2, 3);
+    /// assert_eq!(q.count(), 2);
+    /// ```
+    pub fn new_rect(m: usize, n: usize) -> Queens {
+        // Whichever dimension has fewer lines gets the mandatory
+        // constraint; ties go to columns, matching `new`'s column-first
+        // layout for the square case
+        let cols_mandatory = n <= m;
+        let mandatory = m.min(n);
+
+        let board_total = m
+            .checked_add(n)
+            .expect("queens board too large: m+n overflows usize");
+        let diag_count = board_total
+            .checked_sub(1)
+            .expect("queens board too large: m+n-1 overflows usize");
+        let optional = board_total
+            .checked_sub(mandatory)
+            .and_then(|v| v.checked_add(diag_count.checked_mul(2)?))
+            .and_then(|v| v.checked_add(m.checked_mul(n)?))
+            .expect("queens board too large: optional item count overflows usize");
+
+        let mut solver: Solver = Solver::new_optional(mandatory, optional);
+
+        for r in 1..=m {
+            for c in 1..=n {
+                let con_name = format!("R{}C{}", r, c);
+                let (col_con, row_con) = if cols_mandatory {
+                    (c, n + r)
+                } else {
+                    (m + c, r)
+                };
+                let rd_con = board_total + c + (m - r);
+                let ld_con = board_total + diag_count + (r + c - 1);
+                let is_queen = board_total + 2 * diag_count + (c - 1) * m + r;
+
+                solver.add_option(&con_name, &[col_con, row_con, rd_con, ld_con, is_queen]);
+            }
+        }
+
+        Queens { solver, n: mandatory }
+    }
+
+    /// Creates a new "superqueen" (amazon: queen + knight moves) variant of
+    /// [new](Queens::new): on top of the standard queen constraints, adds an
+    /// optional "at most one" item for every pair of cells a knight's move
+    /// apart, so no two placed queens may attack each other via a knight
+    /// move either
+    ///
+    /// Superqueens admit far fewer non-attacking placements than ordinary
+    /// queens, and at a different threshold -- which makes them a useful
+    /// second data point for the optional/secondary-item machinery, now with
+    /// several optional items shared per option instead of just the two
+    /// diagonals.
+    /// ```
+    ///# use dlx_rs::queens::Queens;
+    /// // A single queen trivially has no knight-move conflicts, but every
+    /// // board from n=2 to n=9 turns out to have no solution at all; the
+    /// // next one after that is n=10, with 4 solutions
+    /// assert_eq!(Queens::new_super(1).count(), 1);
+    /// for i in 2..10 {
+    ///     assert_eq!(Queens::new_super(i).count(), 0);
+    /// }
+    /// assert_eq!(Queens::new_super(10).count(), 4);
+    /// ```
+    pub fn new_super(n: usize) -> Queens {
+        let mandatory = n
+            .checked_mul(2)
+            .expect("queens board too large: 2*n overflows usize");
+        let queen_optional = n
+            .checked_mul(n)
+            .and_then(|nn| nn.checked_add(n.checked_mul(6)?))
+            .and_then(|v| v.checked_sub(2))
+            .expect("queens board too large: n*n + 6*n - 2 overflows usize");
+
+        // Every unordered pair of cells a knight's move apart gets its own
+        // "at most one" item, shared by the two options that could place a
+        // queen on either cell of the pair -- the same trick `new`'s
+        // diagonals already use, just with a pair-specific item instead of
+        // one item per whole diagonal
+        const KNIGHT_MOVES: [(isize, isize); 8] = [
+            (1, 2),
+            (2, 1),
+            (-1, 2),
+            (-2, 1),
+            (1, -2),
+            (2, -1),
+            (-1, -2),
+            (-2, -1),
+        ];
+        let mut knight_items: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        let mut knight_pair_count = 0usize;
+        for r in 1..=n {
+            for c in 1..=n {
+                for &(dr, dc) in &KNIGHT_MOVES {
+                    let r2 = r as isize + dr;
+                    let c2 = c as isize + dc;
+                    if r2 < 1 || c2 < 1 || r2 as usize > n || c2 as usize > n {
+                        continue;
+                    }
+                    let (r2, c2) = (r2 as usize, c2 as usize);
+                    // Visit each unordered pair exactly once, from its
+                    // lexicographically smaller cell
+                    if (r, c) >= (r2, c2) {
+                        continue;
+                    }
+                    knight_pair_count += 1;
+                    let item = queen_optional + knight_pair_count;
+                    knight_items.entry((r, c)).or_default().push(item);
+                    knight_items.entry((r2, c2)).or_default().push(item);
+                }
+            }
+        }
+
+        let optional = queen_optional
+            .checked_add(knight_pair_count)
+            .expect("superqueens board too large: optional item count overflows usize");
+
+        let mut solver: Solver = Solver::new_optional(mandatory, optional);
+
+        for r in 1..=n {
+            for c in 1..=n {
+                let con_name = format!("R{}C{}", r, c);
+                let col_con = c;
+                let row_con = n + r;
+                let rd_con = 2 * n + c - r + n;
+                let ld_con = 4 * n - 1 + r + c - 1;
+                let is_queen = 6 * n - 2 + r + n * (c - 1);
+
+                let mut items = vec![col_con, row_con, rd_con, ld_con, is_queen];
+                if let Some(extra) = knight_items.get(&(r, c)) {
+                    items.extend(extra);
+                }
+
+                solver.add_option(&con_name, &items);
+            }
+        }
+
+        Queens { solver, n }
+    }
+}
+
+impl Queens {
+    /// Returns the next solution as a permutation vector `perm`, where
+    /// `perm[col - 1] = row` gives the row of the queen placed in column
+    /// `col` (both 1-indexed)
+    ///
+    /// Since an N queens solution places exactly one queen per column,
+    /// this is an equivalent, more compact representation of
+    /// [next](Iterator::next)'s `Vec<(usize, usize)>`, and matches how
+    /// solutions are usually reported in the combinatorics literature
+    ///
+    /// ```
+    ///# use dlx_rs::queens::Queens;
+    /// let mut q = Queens::new(4);
+    /// assert_eq!(q.next_as_permutation(), Some(vec![2, 4, 1, 3]));
+    /// ```
+    pub fn next_as_permutation(&mut self) -> Option<Vec<usize>> {
+        let sol = self.next()?;
+        let mut perm = vec![0; self.n];
+        for (r, c) in sol {
+            perm[c - 1] = r;
+        }
+        Some(perm)
+    }
+
+    /// Exhausts the search and serializes every remaining solution as a
+    /// JSON array of `(row, col)` coordinate lists
+    ///
+    /// Eager, like [Solver::solutions_json](crate::solver::Solver::solutions_json):
+    /// the full solution set is collected before being serialized.
+    /// ```
+    ///# use dlx_rs::queens::Queens;
+    /// let mut q = Queens::new(4);
+    /// let json = q.solutions_json();
+    /// let solutions: Vec<Vec<(usize, usize)>> = serde_json::from_str(&json).unwrap();
+    /// assert_eq!(solutions.len(), 2);
+    /// assert_eq!(solutions[0].len(), 4);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn solutions_json(&mut self) -> String {
+        let solutions: Vec<Vec<(usize, usize)>> = self.by_ref().collect();
+        serde_json::to_string(&solutions).expect("Vec<Vec<(usize, usize)>> always serializes")
+    }
+
+    /// Counts the solutions to the `n` queens problem, without requiring a
+    /// caller to build a `Queens` and iterate it by hand
+    ///
+    /// This is exactly [OEIS A000170](https://oeis.org/A000170), "number of
+    /// ways of placing n non-attacking queens on an n X n board": the
+    /// `tests` module below asserts this function against the known
+    /// sequence for `n` in `1..=12`, turning this module into a
+    /// self-validating correctness check for the whole dancing-links
+    /// engine, not just a worked example.
+    ///
+    /// Counts via [solution_slices](crate::solver::Solver::solution_slices)
+    /// rather than collecting every solution, so no per-solution `Vec` or
+    /// `String` allocation happens along the way.
+    /// ```
+    ///# use dlx_rs::queens::Queens;
+    /// assert_eq!(Queens::count_all(8), 92);
+    /// ```
+    pub fn count_all(n: usize) -> usize {
+        let mut queens = Queens::new(n);
+        let mut slices = queens.solver.solution_slices();
+        let mut count = 0;
+        while slices.next().is_some() {
+            count += 1;
+        }
+        count
+    }
 }
 
 impl Iterator for Queens {
@@ -78,3 +315,84 @@ impl Iterator for Queens {
         }
     }
 }
+
+/// `next` forwards directly to the underlying [Solver], which is fused
+impl std::iter::FusedIterator for Queens {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "overflows usize")]
+    fn new_panics_rather_than_overflow() {
+        Queens::new(usize::MAX);
+    }
+
+    #[test]
+    fn new_at_ordinary_size_does_not_panic() {
+        // Far below any overflow boundary, so this should construct as normal
+        let _ = Queens::new(20);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn solutions_json_round_trips_as_coordinates() {
+        let mut q = Queens::new(4);
+        let json = q.solutions_json();
+        let solutions: Vec<Vec<(usize, usize)>> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(solutions, Queens::new(4).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn new_rect_matches_known_rectangular_counts() {
+        // A single queen never attacks itself, so every cell is a solution
+        assert_eq!(Queens::new_rect(1, 4).count(), 4);
+        assert_eq!(Queens::new_rect(4, 1).count(), 4);
+
+        // The only non-attacking placements on a 2x3 board put the two
+        // queens in the two columns furthest apart
+        assert_eq!(Queens::new_rect(2, 3).count(), 2);
+        assert_eq!(Queens::new_rect(3, 2).count(), 2);
+
+        // A square board matches the classic n-queens counts
+        let n_queens_solutions = [0, 1, 0, 0, 2, 10, 4, 40, 92];
+        for (i, &expected) in n_queens_solutions.iter().enumerate().skip(1) {
+            assert_eq!(Queens::new_rect(i, i).count(), expected);
+        }
+    }
+
+    #[test]
+    fn new_super_matches_known_amazon_counts() {
+        // Non-attacking "amazon" (queen + knight) placements: far sparser
+        // than ordinary queens, with no solution at all until n=10
+        let superqueen_solutions = [1, 0, 0, 0, 0, 0, 0, 0, 0, 4];
+        for (i, &expected) in superqueen_solutions.iter().enumerate() {
+            assert_eq!(Queens::new_super(i + 1).count(), expected);
+        }
+    }
+
+    #[test]
+    fn count_all_matches_oeis_a000170() {
+        // https://oeis.org/A000170: number of ways of placing n
+        // non-attacking queens on an n x n board, n = 1..=12
+        let a000170 = [1, 0, 0, 2, 10, 4, 40, 92, 352, 724, 2680, 14200];
+        for (i, &expected) in a000170.iter().enumerate() {
+            let n = i + 1;
+            assert_eq!(Queens::count_all(n), expected, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn num_mandatory_and_optional_match_construction() {
+        // 2*n mandatory items (one row, one column constraint per queen)
+        // and n*n + 6*n - 2 optional ones (diagonals and the "is this
+        // square occupied" items), as computed in Queens::new
+        for n in 1..8 {
+            let q = Queens::new(n);
+            assert_eq!(q.solver.num_mandatory(), 2 * n);
+            assert_eq!(q.solver.num_optional(), n * n + 6 * n - 2);
+        }
+    }
+}
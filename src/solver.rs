@@ -1,7 +1,186 @@
-use std::collections::HashMap;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
 type Index = usize;
 
+/// An item an option covers, paired with the color it claimed for it via
+/// [add_option_colored](Solver::add_option_colored) (`None` for an
+/// ordinary, uncolored item)
+type ColoredItem = (Index, Option<u32>);
+
+/// Canonicalizer passed to [Solver::with_symmetry_pruner]
+type SymmetryPruner = Arc<dyn Fn(&[Index]) -> u64 + Send + Sync>;
+
+/// Errors returned by the higher-level `Solver` APIs which operate after
+/// construction (as opposed to the low-level [cover](Solver::cover)/
+/// [uncover](Solver::uncover) primitives, which keep their existing
+/// `&'static str` errors)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolverError {
+    /// No option exists with the given name
+    UnknownOption(String),
+    /// The named item was covered more than once by a candidate solution
+    ItemOverCovered(Index),
+    /// The named mandatory item was not covered by a candidate solution
+    ItemUncovered(Index),
+    /// An item index fell outside the range of items known to the solver
+    ItemOutOfRange(Index),
+    /// The operation is only valid before iteration has begun
+    AlreadyIterating,
+    /// Adding the option would grow `elements` past the limit set by
+    /// [set_node_limit](Solver::set_node_limit)
+    NodeLimitExceeded(usize),
+    /// An internal dancing-links invariant was violated mid-search (e.g. a
+    /// [cover](Solver::cover)/[uncover](Solver::uncover) call failed). This
+    /// should never happen; if it does, the search halts rather than
+    /// panicking, and the message is preserved here for bug reports
+    Internal(String),
+    /// [from_reader_with_progress](Solver::from_reader_with_progress)
+    /// couldn't parse its input: the header or an option line was missing
+    /// or not in the expected format
+    MalformedInput(String),
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverError::UnknownOption(name) => write!(f, "no option named \"{name}\""),
+            SolverError::ItemOverCovered(item) => write!(f, "item {item} covered more than once"),
+            SolverError::ItemUncovered(item) => write!(f, "item {item} not covered"),
+            SolverError::ItemOutOfRange(item) => write!(f, "item {item} is out of range"),
+            SolverError::AlreadyIterating => {
+                write!(f, "operation is only valid before iteration has begun")
+            }
+            SolverError::NodeLimitExceeded(max) => {
+                write!(f, "adding this option would exceed the node limit of {max}")
+            }
+            SolverError::Internal(msg) => write!(f, "internal dancing-links error: {msg}"),
+            SolverError::MalformedInput(msg) => write!(f, "malformed input: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
+
+/// A plain-data description of a solver's problem: the number of items
+/// (mandatory and optional) and, for every option, its name and the items
+/// it covers, each paired with the color it was
+/// [claimed](Solver::add_option_colored) with, or `None` for an ordinary
+/// (uncolored) item
+///
+/// This is independent of the internal dancing-links linked-list
+/// representation, so it can be compared for equality, serialized, or
+/// handed to [from_description](Solver::from_description) to rebuild an
+/// equivalent (freshly-constructed, not-yet-iterated) solver. Metadata
+/// attached via [add_option_with_meta](Solver::add_option_with_meta) is
+/// not part of the description and is dropped on round-trip.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProblemDescription {
+    pub num_items: usize,
+    pub num_optional: usize,
+    pub options: Vec<(String, Vec<ColoredItem>)>,
+}
+
+/// A single structural difference between two problems, reported by
+/// [diff_problems](Solver::diff_problems)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProblemDiff {
+    /// The two solvers have different total item counts (mandatory plus optional)
+    ItemCountMismatch { this: usize, other: usize },
+    /// The two solvers have different optional item counts
+    OptionalCountMismatch { this: usize, other: usize },
+    /// An option covering this (sorted) item set exists in `self` but has
+    /// no counterpart covering the same items in `other`
+    OnlyInThis { name: String, items: Vec<Index> },
+    /// An option covering this (sorted) item set exists in `other` but has
+    /// no counterpart covering the same items in `self`
+    OnlyInOther { name: String, items: Vec<Index> },
+}
+
+/// A plain-data snapshot of a paused, not-yet-finished search: the option
+/// committed at each level, in order
+///
+/// This only describes a search paused at a *level boundary* -- the point
+/// [step](Solver::step) reaches right after committing a level and before
+/// choosing the next, which is also where [seed_from_solution](Solver::seed_from_solution)
+/// leaves a solver. At that point `self.stage`/`self.idx` carry no
+/// information beyond what the committed options already determine, so
+/// they don't need to be part of this type; a cursor taken mid-row (partway
+/// through covering or uncovering a row's other items) isn't representable
+/// this way. [checkpoint](Solver::checkpoint) records only the committed
+/// levels, whatever stage the search is in: after [step](Solver::step) has
+/// backtracked into a level (run X8 or X6 there), a cursor leaves out which
+/// of that level's options were already tried. [resume](Solver::resume)
+/// only ever leaves a solver at a level boundary.
+///
+/// Obtain one with [checkpoint](Solver::checkpoint); apply it to a fresh,
+/// structurally identical solver with [resume](Solver::resume).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchCursor {
+    /// Search depth; always equal to `committed.len()`
+    pub l: usize,
+    /// Names of the options committed at each level `0..l`, in the order
+    /// [output](Solver::output) would report them
+    pub committed: Vec<String>,
+}
+
+/// Controls which direction [x3x4](Solver::x3x4)/[x6](Solver::x6) walk an
+/// item's column of options, via [set_traversal](Solver::set_traversal)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Traversal {
+    /// Try each item's options top-down, in the order they were added --
+    /// the order Algorithm X uses by default
+    #[default]
+    Natural,
+    /// Try each item's options bottom-up instead. Surfaces a different
+    /// "first" solution than [Natural](Traversal::Natural), useful for
+    /// generation variety and for checking the solver's solution *count*
+    /// doesn't depend on traversal direction
+    Reverse,
+}
+
+/// Controls which item X3 branches on first, via
+/// [set_heuristic](Solver::set_heuristic)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Heuristic {
+    /// Branch on the mandatory item with the fewest remaining covering
+    /// options (Knuth's MRV heuristic) -- the default, and almost always
+    /// the faster choice
+    #[default]
+    Mrv,
+    /// Branch on whichever uncovered mandatory item comes first, ignoring
+    /// how many options cover it
+    ///
+    /// This is a naive, unoptimized DLX: on a hard problem it can be
+    /// dramatically slower than [Mrv](Heuristic::Mrv), since it gives up
+    /// the pruning that comes from tackling the most-constrained item
+    /// first. Useful for a predictable benchmarking baseline, or to match
+    /// the branching order of a reference implementation that doesn't use
+    /// MRV either.
+    FirstFit,
+}
+
+/// A cheap, heuristic classification of how hard a problem looks to solve,
+/// returned by [estimated_difficulty](Solver::estimated_difficulty)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DifficultyClass {
+    /// Items are essentially already forced; a search should finish almost
+    /// immediately
+    Trivial,
+    /// A typical, tractable problem -- comparable to a normal Sudoku puzzle
+    Moderate,
+    /// Densely connected, with few or no items already narrowed down;
+    /// expect the search to take real work
+    Hard,
+    /// So densely connected that fully enumerating every solution is
+    /// unlikely to finish in reasonable time, even if finding *a* solution
+    /// is fast -- comparable to a blank Sudoku grid, which has billions of
+    /// solutions
+    LikelyIntractable,
+}
+
 #[derive(Clone, Debug)]
 enum Link {
     Spacer(Spacer),
@@ -14,6 +193,9 @@ struct OptionElement {
     ulink: Index,
     dlink: Index,
     top: Index,
+    /// Set for nodes added via [add_option_colored](Solver::add_option_colored);
+    /// `None` means this node participates in ordinary (uncolored) covering
+    color: Option<u32>,
 }
 
 #[derive(Clone, Debug)]
@@ -57,7 +239,7 @@ struct Item {
 ///# use dlx_rs::solver::Solver;
 ///# fn main() -> Result<(), Box<dyn Error>> {
 /// // Create Solver with 4 items
-/// let mut s = Solver::new(4);
+/// let mut s: Solver = Solver::new(4);
 /// // Add options
 /// s.add_option("o1", &[1, 2])
 ///     .add_option("o2", &[3])
@@ -74,19 +256,44 @@ struct Item {
 ///     }
 ///# }
 /// ```
+///
+/// `Solver` is generic over an optional per-option metadata type `M`
+/// (defaulting to `()`), see [add_option_with_meta](Solver::add_option_with_meta)
 #[derive(Clone)]
-pub struct Solver {
+pub struct Solver<M = ()> {
     elements: Vec<Link>,
     items: Index,
-    options: HashMap<Index, Vec<Index>>,
+    /// Each option's covered items, paired with the color it claimed that
+    /// item with via [add_option_colored](Solver::add_option_colored)
+    /// (`None` for an ordinary, uncolored item)
+    options: HashMap<Index, Vec<ColoredItem>>,
     l: usize,
     sol_vec: Vec<Index>,
     yielding: bool,
     idx: Index,
-    names: Vec<String>,
+    names: Vec<Arc<str>>,
+    item_names: Vec<Option<String>>,
+    meta: Vec<Option<M>>,
     spacer_ids: HashMap<Index, usize>,
+    spacer_by_index: Vec<Index>,
     stage: Stage,
     optional: Index,
+    started: bool,
+    dup_detection: bool,
+    seen_solutions: HashSet<Vec<String>>,
+    saw_duplicate: bool,
+    symmetry_pruner: Option<SymmetryPruner>,
+    seen_signatures: HashSet<u64>,
+    include_optional_in_mrv: bool,
+    heuristic: Heuristic,
+    traversal: Traversal,
+    node_limit: Option<usize>,
+    last_error: Option<SolverError>,
+    item_order: Option<Vec<Index>>,
+    event_queue: Option<VecDeque<SearchEvent>>,
+    committed_colors: HashMap<Index, u32>,
+    committed_depth: HashMap<Index, usize>,
+    purify_log: HashMap<Index, Vec<Index>>,
 }
 
 /// enum used to determine which stage of the algorithm we are in
@@ -101,7 +308,178 @@ enum Stage {
     X8,
 }
 
-impl fmt::Display for Solver {
+/// Result of a single [step](Solver::step) through the Algorithm X state
+/// machine
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// The search is still running; no solution was yielded and the
+    /// search isn't exhausted yet
+    Continue,
+    /// A solution was found
+    Solution(Vec<String>),
+    /// Every solution has now been found
+    Exhausted,
+}
+
+/// A single semantically meaningful action taken by the Algorithm X search,
+/// as yielded by [events](Solver::events)
+///
+/// Finer-grained than [StepOutcome]: one [step](Solver::step) call (one FSM
+/// stage) can cover several items in a row, which shows up here as one
+/// `Descend` preceded by a `Cover` for each of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SearchEvent {
+    /// An item was covered (removed from further consideration)
+    Cover(Index),
+    /// An item was uncovered (restored) while backtracking
+    Uncover(Index),
+    /// The search committed to an option and moved one level deeper
+    Descend,
+    /// The search left a level to backtrack and try the next alternative
+    Ascend,
+    /// A solution was found
+    Solution(Vec<String>),
+}
+
+/// Hook for observing search progress while [solve_observed](Solver::solve_observed)
+/// drives the Algorithm X state machine, for live instrumentation (progress
+/// bars, counters, logging) without threading extra state through the
+/// search loop itself
+///
+/// Both methods default to doing nothing, so an observer only needs to
+/// implement the events it cares about.
+pub trait Observer {
+    /// Called each time a solution is yielded, with the depth (number of
+    /// options committed) it was found at
+    fn on_solution(&mut self, _depth: usize) {}
+    /// Called each time the search leaves a level to backtrack and try the
+    /// next alternative (Algorithm X's X8 stage)
+    fn on_backtrack(&mut self) {}
+}
+
+/// Decode-level primitives for crates building their own puzzle front-end
+/// on top of [Solver], in the style of this crate's own
+/// [Sudoku](crate::sudoku::Sudoku)/[Queens](crate::queens::Queens)/[Aztec](crate::aztec::Aztec)
+///
+/// Sealed (see the private `Sealed` supertrait) so it can only ever be
+/// implemented for [Solver] itself -- new methods can be added here in a
+/// minor release without that being a breaking change for downstream
+/// crates, since none of them can provide their own implementation anyway.
+/// Front-ends only ever need `Solver`'s existing `pub` methods (adding
+/// options, `next`/`solve`, `output*`); this trait exists for the rarer
+/// case of introspecting an option's covered items by name without
+/// reimplementing the name-to-item lookup every puzzle module would
+/// otherwise duplicate.
+pub trait SolverExt: sealed::Sealed {
+    /// Returns the items covered by the option named `name`, or `None` if
+    /// no such option was ever added
+    ///
+    /// ```
+    ///# use dlx_rs::solver::{Solver, SolverExt};
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    /// s.add_option("o1", &[1, 3]);
+    ///
+    /// assert_eq!(s.items_for_option("o1"), Some(vec![1, 3]));
+    /// assert_eq!(s.items_for_option("unknown"), None);
+    /// ```
+    fn items_for_option(&self, name: &str) -> Option<Vec<Index>>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl<M> Sealed for super::Solver<M> {}
+}
+
+impl<M> SolverExt for Solver<M> {
+    fn items_for_option(&self, name: &str) -> Option<Vec<Index>> {
+        let (&spacer, _) = self
+            .spacer_ids
+            .iter()
+            .find(|(_, &id)| self.names[id].as_ref() == name)?;
+        Some(self.options[&spacer].iter().map(|&(item, _)| item).collect())
+    }
+}
+
+impl<M> Solver<M> {
+    /// Computes the comparable "problem definition" of this solver: item
+    /// count, mandatory/optional boundary, and every option's name paired
+    /// with its covered (item, color) pairs sorted into a canonical order,
+    /// with the options themselves also sorted -- so that construction
+    /// order has no bearing on the result, but two options that differ only
+    /// in which color they claim an item with still compare unequal. Used
+    /// by [PartialEq](Solver::eq).
+    fn problem_key(&self) -> (usize, Index, Vec<(String, Vec<ColoredItem>)>) {
+        let mut by_name_id: Vec<(usize, Index)> = self
+            .spacer_ids
+            .iter()
+            .map(|(&spacer, &name_id)| (name_id, spacer))
+            .collect();
+        by_name_id.sort_unstable_by_key(|&(name_id, _)| name_id);
+
+        let mut options: Vec<(String, Vec<ColoredItem>)> = by_name_id
+            .into_iter()
+            .map(|(name_id, spacer)| {
+                let mut items = self.options[&spacer].clone();
+                items.sort_unstable();
+                (self.names[name_id].to_string(), items)
+            })
+            .collect();
+        options.sort_unstable();
+
+        (self.items, self.optional, options)
+    }
+}
+
+/// Compares solvers by their *problem definition* -- item count, the
+/// mandatory/optional boundary, and the set of options (each as an
+/// unordered item-set) -- not by the internal dancing-links layout or
+/// search progress
+///
+/// Two solvers built by adding the same options in a different order
+/// compare equal, since insertion order isn't part of the problem being
+/// solved; `add_option`-returned names are otherwise compared exactly, so
+/// a renamed option makes solvers unequal even if the items it covers are
+/// identical. Search state (`self.l`, `self.sol_vec`, `started`, ...) and
+/// per-option [metadata](Solver::add_option_with_meta) are not compared
+/// either, matching [ProblemDescription]'s round-trip contract.
+impl<M> PartialEq for Solver<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.problem_key() == other.problem_key()
+    }
+}
+
+impl<M> Eq for Solver<M> {}
+
+/// Hashes by the same *problem definition* [PartialEq](Solver::eq) compares
+/// by, so that equal solvers (same items, same options, regardless of
+/// construction order) also hash equal, as `Hash` requires
+///
+/// ```
+///# use dlx_rs::solver::Solver;
+///# use std::collections::hash_map::DefaultHasher;
+///# use std::hash::{Hash, Hasher};
+/// let mut a: Solver = Solver::new(2);
+/// a.add_option("o1", &[1]).add_option("o2", &[2]);
+///
+/// let mut b: Solver = Solver::new(2);
+/// b.add_option("o2", &[2]).add_option("o1", &[1]);
+///
+/// assert!(a == b);
+///
+/// let mut ha = DefaultHasher::new();
+/// a.hash(&mut ha);
+/// let mut hb = DefaultHasher::new();
+/// b.hash(&mut hb);
+/// assert_eq!(ha.finish(), hb.finish());
+/// ```
+impl<M> Hash for Solver<M> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.problem_key().hash(state);
+    }
+}
+
+impl<M> fmt::Display for Solver<M> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // First write columns
         let mut last_col = 1;
@@ -114,7 +492,7 @@ impl fmt::Display for Solver {
         while index != 0 {
             linked_items.insert(index, col_num);
             col_num += 1;
-            write!(f, "{} ", index).unwrap();
+            write!(f, "{} ", self.item_label(index)).unwrap();
             index = self.elements[index].r();
         }
 
@@ -133,7 +511,7 @@ impl fmt::Display for Solver {
                         //    println!("Cur_col: {}, last col: {}", cur_col, last_col);
                         let del = 2 * (1 + cur_col - last_col);
                         //    println!("del: {}",del);
-                        write!(f, "{:del$}", i.top()).unwrap();
+                        write!(f, "{:del$}", self.item_label(i.top())).unwrap();
                         last_col = cur_col + 1;
                     };
                 }
@@ -228,6 +606,15 @@ impl Link {
             Link::Item(x) => x.l,
         }
     }
+    /// The color tag attached by [add_option_colored](Solver::add_option_colored),
+    /// if any; `None` for every node outside that feature (and always for
+    /// `Spacer`/`Item` nodes)
+    fn color(&self) -> Option<u32> {
+        match self {
+            Link::OptionElement(x) => x.color,
+            _ => None,
+        }
+    }
 }
 /*
 impl Link for Spacer {
@@ -237,13 +624,43 @@ impl Link for Spacer {
 }
 */
 
-impl Solver {
+impl<M> Solver<M> {
     /// Returns a solver with `n` items, all of which must be covered exactly
     /// once
     pub fn new(n: Index) -> Self {
         Self::new_optional(n, 0)
     }
 
+    /// Returns a solver with `names.len()` mandatory items, pre-named via
+    /// [with_item_names](Solver::with_item_names)
+    ///
+    /// The capstone of the named-items API: build the whole problem in
+    /// terms of names and the [Display](fmt::Display) output, solutions,
+    /// and [item_name](Solver::item_name) lookups all read back in those
+    /// same names, with the index-based API still underneath for anything
+    /// that needs it.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// // A tiny scheduling problem: three days, each covered by exactly
+    /// // one shift -- built and read back entirely in terms of names
+    /// let mut s: Solver = Solver::new_with_item_names(&["monday", "tuesday", "wednesday"]);
+    /// s.add_option("alice-mon-tue", &[1, 2])
+    ///     .add_option("bob-wed", &[3])
+    ///     .add_option("alice-wed", &[3]);
+    ///
+    /// // Display renders the named items, not their raw indices
+    /// assert!(s.to_string().starts_with(" monday tuesday wednesday"));
+    ///
+    /// let solution = s.next().unwrap();
+    /// assert_eq!(solution, vec!["alice-mon-tue".to_string(), "bob-wed".to_string()]);
+    /// ```
+    pub fn new_with_item_names(names: &[&str]) -> Self {
+        let mut solver = Self::new(names.len());
+        solver.with_item_names(names);
+        solver
+    }
+
     /// Returns a solver with `n` mandatory items and `m` optional items to be covered
     /// This allows us to include items which may or may not be covered (but
     /// still may not be covered more than once)
@@ -264,7 +681,7 @@ impl Solver {
     /// ```
     ///# use dlx_rs::solver::Solver;
     ///
-    /// let mut s = Solver::new_optional(4,1);
+    /// let mut s: Solver = Solver::new_optional(4,1);
     ///
     /// s.add_option("o1", &[1, 3])
     ///     .add_option("o2", &[2, 4])
@@ -325,13 +742,200 @@ impl Solver {
             items: n,
             options: HashMap::new(),
             l: 0,
-            sol_vec: vec![],
+            // No solution ever uses more options than there are mandatory
+            // items to cover, so this is sized up front rather than growing
+            // by one for every option added (which over-allocates heavily
+            // for option-heavy problems like Sudoku)
+            sol_vec: vec![0; mandatory],
             names: vec![],
+            item_names: vec![None; n + 1],
+            meta: vec![],
             spacer_ids: HashMap::new(),
+            spacer_by_index: vec![],
             yielding: true,
             idx: 0,
             stage: Stage::X2,
+            started: false,
+            dup_detection: false,
+            seen_solutions: HashSet::new(),
+            saw_duplicate: false,
+            symmetry_pruner: None,
+            seen_signatures: HashSet::new(),
+            include_optional_in_mrv: false,
+            heuristic: Heuristic::Mrv,
+            traversal: Traversal::Natural,
+            node_limit: None,
+            last_error: None,
+            item_order: None,
+            event_queue: None,
+            committed_colors: HashMap::new(),
+            committed_depth: HashMap::new(),
+            purify_log: HashMap::new(),
+        }
+    }
+
+    /// Rebuilds a fresh, not-yet-iterated solver from a
+    /// [ProblemDescription](ProblemDescription), e.g. one produced earlier
+    /// by [into_problem_description](Solver::into_problem_description)
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2, 3]);
+    ///
+    /// let description = s.clone().into_problem_description();
+    /// let rebuilt: Solver = Solver::from_description(&description);
+    ///
+    /// assert_eq!(rebuilt.into_problem_description(), description);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `description.num_optional` exceeds `description.num_items`
+    /// -- a malformed description that would otherwise underflow into a
+    /// nonsensical mandatory-item count and silently wreck the
+    /// mandatory/optional boundary [x2](Solver::x2) and [x3x4](Solver::x3x4)
+    /// rely on
+    pub fn from_description(description: &ProblemDescription) -> Self {
+        let mandatory = description
+            .num_items
+            .checked_sub(description.num_optional)
+            .expect("malformed ProblemDescription: num_optional exceeds num_items");
+        let mut solver = Self::new_optional(mandatory, description.num_optional);
+        for (name, option) in &description.options {
+            let plain: Vec<Index> = option
+                .iter()
+                .filter(|&&(_, color)| color.is_none())
+                .map(|&(item, _)| item)
+                .collect();
+            let colored: Vec<(Index, u32)> = option
+                .iter()
+                .filter_map(|&(item, color)| color.map(|c| (item, c)))
+                .collect();
+            if colored.is_empty() {
+                solver.add_option(name, &plain);
+            } else {
+                solver.add_option_colored(name, &plain, &colored);
+            }
+        }
+        solver
+    }
+
+    /// Rebuilds a fresh, not-yet-iterated solver from a textbook boolean
+    /// exact-cover matrix: `names[i]` labels option `i`, and `matrix[i][j]`
+    /// is `true` iff option `i` covers item `j + 1`
+    ///
+    /// This is the inverse of any caller's own boolean-matrix encoding of
+    /// an exact-cover problem (e.g. [Sudoku::to_matrix](crate::sudoku::Sudoku::to_matrix)).
+    /// Every item here is mandatory, matching the plain matrix
+    /// formulation; for a solver with optional items, round-trip through
+    /// [ProblemDescription] instead.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let names = vec!["o1".to_string(), "o2".to_string()];
+    /// let matrix = vec![vec![true, false], vec![false, true]];
+    /// let mut s: Solver = Solver::from_matrix(&names, &matrix);
+    ///
+    /// assert_eq!(s.next(), Some(vec!["o1".to_string(), "o2".to_string()]));
+    /// ```
+    pub fn from_matrix(names: &[String], matrix: &[Vec<bool>]) -> Self {
+        let num_items = matrix.first().map_or(0, |row| row.len());
+        let mut solver = Self::new(num_items);
+        for (name, row) in names.iter().zip(matrix) {
+            let option: Vec<Index> = row
+                .iter()
+                .enumerate()
+                .filter_map(|(i, &covers)| covers.then_some(i + 1))
+                .collect();
+            solver.add_option(name, &option);
+        }
+        solver
+    }
+
+    /// Builds a solver by streaming options from `r` line by line, instead
+    /// of requiring the whole problem already in memory
+    ///
+    /// The expected format is a header line `mandatory optional nodes_hint`
+    /// (the first two counts are what [new_optional](Solver::new_optional)
+    /// takes; `nodes_hint` is an estimate of the final `elements.len()`,
+    /// used to [reserve](Vec::reserve) capacity up front), followed by one
+    /// option per line: its name followed by the items it covers, all
+    /// whitespace-separated, e.g.
+    /// ```text
+    /// 4 1 14
+    /// o1 1 3
+    /// o2 2 4
+    /// o3 1 5
+    /// o4 3
+    /// o5 3 5
+    /// ```
+    /// `progress` is called with the number of options parsed so far after
+    /// every `progress_every` option lines -- for a progress bar on a file
+    /// with millions of options, too large to size up any other way.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///# use std::io::Cursor;
+    ///
+    /// let input = "4 1 14\no1 1 3\no2 2 4\no3 1 5\no4 3\no5 3 5\n";
+    /// let mut progress_calls = Vec::new();
+    /// let mut s: Solver = Solver::from_reader_with_progress(
+    ///     Cursor::new(input),
+    ///     2,
+    ///     |n| progress_calls.push(n),
+    /// ).unwrap();
+    ///
+    /// assert_eq!(progress_calls, vec![2, 4]);
+    /// assert_eq!(s.next(), Some(vec!["o2".to_string(), "o1".to_string()]));
+    /// ```
+    pub fn from_reader_with_progress<R: std::io::BufRead, F: FnMut(usize)>(
+        r: R,
+        progress_every: usize,
+        mut progress: F,
+    ) -> Result<Self, SolverError> {
+        let mut lines = r.lines();
+
+        let header = lines
+            .next()
+            .ok_or_else(|| SolverError::MalformedInput("missing header line".to_string()))?
+            .map_err(|e| SolverError::MalformedInput(e.to_string()))?;
+        let mut header_parts = header.split_whitespace();
+        let parse_count = |part: Option<&str>, what: &str| {
+            part.and_then(|s| s.parse().ok())
+                .ok_or_else(|| SolverError::MalformedInput(format!("missing or invalid {what} in header")))
+        };
+        let mandatory: Index = parse_count(header_parts.next(), "mandatory item count")?;
+        let optional: Index = parse_count(header_parts.next(), "optional item count")?;
+        let nodes_hint: usize = parse_count(header_parts.next(), "nodes hint")?;
+
+        let mut solver = Self::new_optional(mandatory, optional);
+        solver.elements.reserve(nodes_hint.saturating_sub(solver.elements.len()));
+
+        let mut count = 0;
+        for line in lines {
+            let line = line.map_err(|e| SolverError::MalformedInput(e.to_string()))?;
+            let mut parts = line.split_whitespace();
+            let name = parts.next().ok_or_else(|| {
+                SolverError::MalformedInput(format!("option line {} is empty", count + 1))
+            })?;
+            let items: Vec<Index> = parts
+                .map(|p| {
+                    p.parse().map_err(|_| {
+                        SolverError::MalformedInput(format!("invalid item index \"{p}\""))
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+
+            solver.add_option(name, &items);
+            count += 1;
+            if progress_every > 0 && count % progress_every == 0 {
+                progress(count);
+            }
         }
+
+        Ok(solver)
     }
 
     /// Adds an option which would cover items defined by `option`, and with name `name
@@ -358,25 +962,132 @@ impl Solver {
     ///        ⥯      ⥯     ⥯     ⥯
     /// ```
     pub fn add_option(&mut self, name: &str, option: &[Index]) -> &mut Self {
-        // Increase max depth, come back to this later
-        self.sol_vec.push(0);
-        //        self.sol_vec.push(0);
+        self.add_option_impl(name, option, None)
+    }
+
+    /// Like [add_option](Solver::add_option), but additionally attaches a
+    /// piece of user-supplied metadata `M` to the option, retrievable later
+    /// from [output_meta](Solver::output_meta)
+    ///
+    /// This is useful for front-ends (as Sudoku/Queens/Aztec do) that would
+    /// otherwise have to format a name string and then parse it back to
+    /// recover structured data
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver<(usize, usize)> = Solver::new(2);
+    /// s.add_option_with_meta("o1", &[1], (1, 1))
+    ///     .add_option_with_meta("o2", &[2], (2, 2));
+    ///
+    /// s.next();
+    /// assert_eq!(s.output_meta(), vec![Some(&(1, 1)), Some(&(2, 2))]);
+    /// ```
+    pub fn add_option_with_meta(&mut self, name: &str, option: &[Index], meta: M) -> &mut Self {
+        self.add_option_impl(name, option, Some(meta))
+    }
+
+    /// Like [add_option](Solver::add_option), but additionally lets this
+    /// option claim a *color* on some items instead of covering them
+    /// outright, implementing the "colored items" extension to dancing
+    /// links from Knuth's Algorithm C
+    ///
+    /// `items` are covered exactly as [add_option](Solver::add_option)
+    /// would: at most one option touching any of them may appear in a
+    /// solution. `colored` pairs instead let any number of options agree
+    /// to share an item, as long as they all name the *same* color for
+    /// it -- the first option selected that colors a given item commits
+    /// it to that color and removes every other not-yet-chosen option
+    /// that would color it differently; later options naming the same
+    /// color are then free to use it too. A `colored` item should be one
+    /// of this solver's *optional* items (see [new_optional](Solver::new_optional)),
+    /// since, as with any optional item, nothing requires it to appear in
+    /// a solution at all.
+    ///
+    /// This only affects the backtracking search driven by
+    /// [next](Solver::next)/[solve](Solver::solve)/[step](Solver::step):
+    /// [select](Solver::select), [select_matching](Solver::select_matching)
+    /// and [min_set_cover](Solver::min_set_cover) treat a colored item like
+    /// any other, covering it outright rather than negotiating a color.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// // Items 1 and 2 are mandatory; item 3 is optional and colored
+    /// let mut s: Solver = Solver::new_optional(2, 1);
+    /// s.add_option_colored("o1", &[1], &[(3, 7)])
+    ///     .add_option_colored("o2", &[2], &[(3, 7)])
+    ///     .add_option_colored("o3", &[2], &[(3, 9)]);
+    ///
+    /// // o1 and o2 agree on color 7 for item 3, so they coexist; o3 wants
+    /// // a different color for the same item, so it can never join o1
+    /// assert_eq!(s.next(), Some(vec!["o1".to_string(), "o2".to_string()]));
+    /// assert_eq!(s.next(), None);
+    /// ```
+    pub fn add_option_colored(
+        &mut self,
+        name: &str,
+        items: &[Index],
+        colored: &[(Index, u32)],
+    ) -> &mut Self {
+        self.add_option_impl_colored(name, items, colored, None)
+    }
+
+    /// Like [add_option](Solver::add_option), but returns a
+    /// [SolverError::NodeLimitExceeded] instead of growing `elements` past
+    /// the limit set by [set_node_limit](Solver::set_node_limit)
+    ///
+    /// Every option adds one internal node per covered item plus one
+    /// closing spacer, so `elements.len()` grows by `option.len() + 1` per
+    /// call; this checks that growth against the limit before it happens.
+    /// With no limit set, this always succeeds, exactly like `add_option`.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::{Solver, SolverError};
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.set_node_limit(6);
+    ///
+    /// assert!(s.add_option_checked("o1", &[1]).is_ok());
+    /// assert!(matches!(
+    ///     s.add_option_checked("o2", &[1, 2]),
+    ///     Err(SolverError::NodeLimitExceeded(6))
+    /// ));
+    /// ```
+    pub fn add_option_checked(
+        &mut self,
+        name: &str,
+        option: &[Index],
+    ) -> Result<&mut Self, SolverError> {
+        if let Some(max) = self.node_limit {
+            if self.elements.len() + option.len() + 1 > max {
+                return Err(SolverError::NodeLimitExceeded(max));
+            }
+        }
+        Ok(self.add_option_impl(name, option, None))
+    }
+
+    fn add_option_impl(&mut self, name: &str, option: &[Index], meta: Option<M>) -> &mut Self {
+        self.add_option_impl_colored(name, option, &[], meta)
+    }
 
+    fn add_option_impl_colored(
+        &mut self,
+        name: &str,
+        option: &[Index],
+        colored: &[(Index, u32)],
+        meta: Option<M>,
+    ) -> &mut Self {
         // Now add elements from the option
 
-        for &item_id in option {
-            let new_ulink = self.elements[item_id].u();
-            let new_id = self.elements.len();
-            self.elements[new_ulink].set_d(new_id);
-            self.elements[item_id].set_u(new_id);
-            self.elements[item_id].inc_l();
-            let new_node = Link::OptionElement(OptionElement {
-                ulink: new_ulink,
-                dlink: item_id,
-                top: item_id,
-            });
+        let mut all_items = Vec::with_capacity(option.len() + colored.len());
 
-            self.elements.push(new_node);
+        for &item_id in option {
+            self.push_option_element(item_id, None);
+            all_items.push((item_id, None));
+        }
+        for &(item_id, color) in colored {
+            self.push_option_element(item_id, Some(color));
+            all_items.push((item_id, Some(color)));
         }
 
         //Add spacer at the end
@@ -396,503 +1107,3891 @@ impl Solver {
         self.elements[root_spacer_index].set_u(spacer_index);
 
         // Add the entry to the hash table
-        self.options.insert(spacer_index, option.to_vec());
-        self.names.push(String::from(name));
+        self.options.insert(spacer_index, all_items);
+        self.names.push(Arc::from(name));
+        self.meta.push(meta);
         self.spacer_ids.insert(spacer_index, self.names.len() - 1);
+        // `select`/`select_matching` walk forward from the *preceding*
+        // spacer (root, for the very first option) to reach this row's
+        // elements, not from this row's own closing spacer -- so cache
+        // `bottom_spacer_index`, the sentinel this row was appended after
+        self.spacer_by_index.push(bottom_spacer_index);
 
         self
     }
 
-    /// Covers item in column `i`
-    /// i.e. `cover(2)` would transform
+    /// Links a single new [OptionElement] node for `item_id` into that
+    /// item's column, optionally tagged with a [color](Solver::add_option_colored)
+    fn push_option_element(&mut self, item_id: Index, color: Option<u32>) {
+        let new_ulink = self.elements[item_id].u();
+        let new_id = self.elements.len();
+        self.elements[new_ulink].set_d(new_id);
+        self.elements[item_id].set_u(new_id);
+        self.elements[item_id].inc_l();
+        let new_node = Link::OptionElement(OptionElement {
+            ulink: new_ulink,
+            dlink: item_id,
+            top: item_id,
+            color,
+        });
+
+        self.elements.push(new_node);
+    }
+
+    /// Cyclically shifts the vertical (option) order under every item by
+    /// `offset` positions.
     ///
-    /// ```text
-    /// i0  ⟷  i1  ⟷  i2  ⟷  i3  ⟷  i4
-    ///        ⥯      ⥯     ⥯     ⥯   s0
-    /// o1     ⦿      ⦿     ⥯     ⥯   s1
-    /// o2     ⥯      ⥯     ⦿     ⥯   s2
-    /// o3     ⥯      ⦿     ⥯     ⦿   s3
-    /// o4     ⦿      ⥯     ⥯     ⥯   s4
-    ///        ⥯      ⥯     ⥯     ⥯
-    /// ```
-    /// into
+    /// This does not change which options exist, only the order in which
+    /// [x3x4](Solver::x3x4) first tries them, so the *first* solution found
+    /// by a subsequent call to [next](Solver::next) can be varied
+    /// deterministically (and reproducibly, unlike full randomization)
+    /// without disturbing the exact-cover structure.
     ///
-    /// ```text
-    /// i0  ⟷  i1  ⟷  ⟷  ⟷  i3  ⟷  i4
-    ///        ⥯            ⥯     ⥯   s0
-    /// o1     ⦿            ⥯     ⥯   s1
-    /// o2     ⥯            ⦿     ⥯   s2
-    /// o3     ⥯            ⥯     ⦿   s3
-    /// o4     ⦿            ⥯     ⥯   s4
-    ///        ⥯            ⥯     ⥯
-    /// ```
-    pub fn cover(&mut self, i: Index) -> Result<(), &'static str> {
-        let col = &mut self.elements[i];
-        match col {
-            Link::Item(_) => {}
-            _ => return Err("Can only cover items"),
-        };
-        // Hide all of the options in col i
-        let mut p = col.d();
-        while p != i {
-            self.hide(p)?;
-            p = self.elements[p].d();
+    /// Must be called before iteration begins, since it walks the live
+    /// `ulink`/`dlink` chains.
+    pub fn rotate_option_order(&mut self, offset: usize) -> Result<(), SolverError> {
+        if self.started {
+            return Err(SolverError::AlreadyIterating);
+        }
+        for i in 1..=self.items {
+            let len = self.elements[i].get_l();
+            if len == 0 {
+                continue;
+            }
+            for _ in 0..(offset % len) {
+                self.rotate_column_once(i);
+            }
         }
-
-        // Unlink item
-        self.unlink_item(i);
-        //let l = self.elements[i].l();
-        //let r = self.elements[i].r();
-        //self.elements[l].set_r(r);
-        //self.elements[r].set_l(l);
-
         Ok(())
     }
 
-    /// Unlinks an item from the horizontally linked list
-    fn unlink_item(&mut self, i: Index) {
-        let l = self.elements[i].l();
-        let r = self.elements[i].r();
-        self.elements[l].set_r(r);
-        self.elements[r].set_l(l);
-    }
+    /// Moves the option currently at the head of item `i`'s vertical list to
+    /// the tail, preserving the circular doubly-linked invariant
+    fn rotate_column_once(&mut self, i: Index) {
+        let first = self.elements[i].d();
+        if first == i {
+            return;
+        }
+        let second = self.elements[first].d();
+        let last = self.elements[i].u();
 
-    /// Relinks an item into the horizontally linked list
-    ///
-    /// Must be done in the reverse order to unlinking
-    fn relink_item(&mut self, i: Index) {
-        let l = self.elements[i].l();
-        let r = self.elements[i].r();
-        self.elements[l].set_r(i);
-        self.elements[r].set_l(i);
-    }
+        // Unlink first from the head
+        self.elements[i].set_d(second);
+        self.elements[second].set_u(i);
 
-    /// When selecting an option, this runs through all of the items it covers
-    /// and unlinks those OptionElements vertically
-    fn hide(&mut self, p: Index) -> Result<(), &'static str> {
-        let mut q = p + 1;
-        while q != p {
-            let x = self.elements[q].top();
-            let u = self.elements[q].u();
-            let d = self.elements[q].d();
+        // Relink first at the tail
+        self.elements[last].set_d(first);
+        self.elements[first].set_u(last);
+        self.elements[first].set_d(i);
+        self.elements[i].set_u(first);
+    }
 
-            match self.elements[q] {
-                Link::Item(_) => return Err("Hide encountered and item"),
-                Link::Spacer(_) => q = u,
-                Link::OptionElement(_) => {
-                    self.elements[u].set_d(d);
-                    self.elements[d].set_u(u);
-                    self.elements[x].dec_l();
-                }
-            };
-            q += 1;
+    /// Returns `true` if item `i` is still active, i.e. linked into the
+    /// horizontal item list and so not yet covered
+    pub fn is_item_active(&self, i: Index) -> bool {
+        let mut idx = self.elements[0].r();
+        while idx != 0 {
+            if idx == i {
+                return true;
+            }
+            idx = self.elements[idx].r();
         }
+        false
+    }
 
-        Ok(())
+    /// Returns the number of options currently covering item `i`
+    pub fn item_option_count(&self, i: Index) -> usize {
+        self.elements[i].get_l()
     }
 
-    /// Reverse of function [cover](crate::solver::Solver::cover)
-    pub fn uncover(&mut self, i: Index) -> Result<(), &'static str> {
-        // Relink item
-        self.relink_item(i);
-        //let l = self.elements[i].l();
-        //let r = self.elements[i].r();
-        //self.elements[l].set_r(i);
-        //self.elements[r].set_l(i);
-
-        let col = &mut self.elements[i];
-
-        match col {
-            Link::Item(_) => {}
-            _ => return Err("Can only uncover items"),
-        };
-
-        // Hide all of the options in col i
-        let mut p = col.u();
+    /// Returns the names of every option currently covering item `i`, in
+    /// column order
+    ///
+    /// Where [item_option_count](Solver::item_option_count) gives the bare
+    /// count, this gives the actual candidates -- e.g. useful for a Sudoku
+    /// cell's remaining pencil marks. Walks item `i`'s vertical chain the
+    /// same way [cover](Solver::cover) does, so it reflects whatever has
+    /// been covered so far, not the item's original degree.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1, 2]).add_option("o2", &[1]).add_option("o3", &[2]);
+    ///
+    /// assert_eq!(s.options_for_item(1), vec!["o1".to_string(), "o2".to_string()]);
+    /// ```
+    pub fn options_for_item(&self, i: Index) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut p = self.elements[i].d();
         while p != i {
-            self.unhide(p)?;
-            p = self.elements[p].u();
+            let spacer = self.spacer_for(p);
+            names.push(self.names[self.spacer_ids[&spacer]].to_string());
+            p = self.elements[p].d();
         }
+        names
+    }
 
-        Ok(())
+    /// Reports how many items are currently covered by exactly `k` options,
+    /// as a map from degree `k` to item count
+    ///
+    /// Called right after construction (before any [cover](Solver::cover)
+    /// or search step has run), this is a cheap structural read of the
+    /// problem: a histogram skewed toward low degrees predicts easy MRV
+    /// branching, since some item will quickly narrow to very few choices,
+    /// while a flat histogram predicts a harder search. Degrees only ever
+    /// shrink as items are covered mid-search, so calling this later
+    /// reports the *current* degrees rather than the original ones.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///# use std::collections::BTreeMap;
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    /// s.add_option("o1", &[1, 2])
+    ///     .add_option("o2", &[1, 3])
+    ///     .add_option("o3", &[2, 3])
+    ///     .add_option("o4", &[3]);
+    ///
+    /// // Items 1 and 2 are each covered by 2 options, item 3 by 3
+    /// assert_eq!(s.item_degree_histogram(), BTreeMap::from([(2, 2), (3, 1)]));
+    /// ```
+    pub fn item_degree_histogram(&self) -> std::collections::BTreeMap<usize, usize> {
+        let mut histogram = std::collections::BTreeMap::new();
+        for i in 1..=self.items {
+            *histogram.entry(self.item_option_count(i)).or_insert(0) += 1;
+        }
+        histogram
     }
 
-    /// Reverse of function [hide](crate::solver::Solver::hide)
-    fn unhide(&mut self, p: Index) -> Result<(), &'static str> {
-        let mut q = p - 1;
-        while q != p {
-            let x = self.elements[q].top();
-            let u = self.elements[q].u();
-            let d = self.elements[q].d();
+    /// Returns every other option that shares at least one item with the
+    /// named option, i.e. every option that cannot coexist with it in the
+    /// same solution
+    ///
+    /// A plain combinatorial read over the stored `self.options` item sets
+    /// -- no search involved. Useful for building a constraint-graph view
+    /// of a problem: for a Sudoku placement, this reports the other
+    /// placements sharing its cell, row, column or box. Results are sorted
+    /// by name, since `self.options` is a `HashMap` with no stable order
+    /// of its own. Returns an empty vector for an unknown option name.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    /// s.add_option("o1", &[1, 2])
+    ///     .add_option("o2", &[1, 3])
+    ///     .add_option("o3", &[2, 3])
+    ///     .add_option("o4", &[3]);
+    ///
+    /// // o1 shares item 1 with o2 and item 2 with o3, but shares nothing with o4
+    /// assert_eq!(s.option_conflicts("o1"), vec!["o2", "o3"]);
+    /// ```
+    pub fn option_conflicts(&self, name: &str) -> Vec<String> {
+        let Some((&spacer, _)) = self
+            .spacer_ids
+            .iter()
+            .find(|(_, &id)| &*self.names[id] == name)
+        else {
+            return Vec::new();
+        };
+        let items: Vec<Index> = self.options[&spacer].iter().map(|&(item, _)| item).collect();
 
-            match self.elements[q] {
-                Link::Item(_) => return Err("Hide encountered and item"),
-                Link::Spacer(_) => q = d,
-                Link::OptionElement(_) => {
-                    self.elements[u].set_d(q);
-                    self.elements[d].set_u(q);
-                    self.elements[x].inc_l();
-                }
-            };
-            q -= 1;
+        let mut conflicts: Vec<String> = self
+            .options
+            .iter()
+            .filter(|(&other_spacer, _)| other_spacer != spacer)
+            .filter(|(_, other_items)| {
+                other_items
+                    .iter()
+                    .any(|&(item, _)| items.contains(&item))
+            })
+            .map(|(other_spacer, _)| self.names[self.spacer_ids[other_spacer]].to_string())
+            .collect();
+        conflicts.sort();
+        conflicts
+    }
+
+    /// Classifies how hard this problem looks to solve, from cheap
+    /// structural metrics alone: the average item degree (mean options per
+    /// item, from [item_degree_histogram](Solver::item_degree_histogram))
+    /// and the fraction of items already narrowed down to a single option
+    ///
+    /// This is a heuristic, not a guarantee -- a problem with a low average
+    /// degree can still have a pathological search, and vice versa -- but
+    /// it's a useful cheap check before committing to a full search on an
+    /// unfamiliar or user-supplied instance, e.g. to decide whether to set
+    /// a [node limit](Solver::set_node_limit) or reach for
+    /// [count_up_to_parallel](Solver::count_up_to_parallel) instead of
+    /// exhaustively enumerating.
+    ///
+    /// A fresh blank Sudoku, where every item is covered by exactly 9
+    /// options and none are yet narrowed down, lands solidly in
+    /// [LikelyIntractable](DifficultyClass::LikelyIntractable) -- fully
+    /// enumerating its roughly 6.7 sextillion solutions is not going to
+    /// finish. A Sudoku with enough givens filled in (which narrows many
+    /// items' degrees right away) instead lands in
+    /// [Moderate](DifficultyClass::Moderate).
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    ///# use dlx_rs::solver::DifficultyClass;
+    ///
+    /// let blank = Sudoku::new(3);
+    /// assert_eq!(blank.solver.estimated_difficulty(), DifficultyClass::LikelyIntractable);
+    ///
+    /// let given = vec![
+    ///     5, 3, 0, 0, 7, 0, 0, 0, 0, 6, 0, 0, 1, 9, 5, 0, 0, 0, 0, 9, 8, 0, 0, 0, 0, 6, 0, 8, 0,
+    ///     0, 0, 6, 0, 0, 0, 3, 4, 0, 0, 8, 0, 3, 0, 0, 1, 7, 0, 0, 0, 2, 0, 0, 0, 6, 0, 6, 0, 0,
+    ///     0, 0, 2, 8, 0, 0, 0, 0, 4, 1, 9, 0, 0, 5, 0, 0, 0, 0, 8, 0, 0, 7, 9,
+    /// ];
+    /// let s = Sudoku::new_from_input(&given).unwrap();
+    /// assert_eq!(s.solver.estimated_difficulty(), DifficultyClass::Moderate);
+    /// ```
+    pub fn estimated_difficulty(&self) -> DifficultyClass {
+        if self.items == 0 {
+            return DifficultyClass::Trivial;
         }
 
-        Ok(())
-    }
+        let histogram = self.item_degree_histogram();
+        let total_degree: usize = histogram.iter().map(|(&degree, &count)| degree * count).sum();
+        let avg_degree = total_degree as f64 / self.items as f64;
+        let degree_one_items = histogram.get(&1).copied().unwrap_or(0);
+        let degree_one_fraction = degree_one_items as f64 / self.items as f64;
 
-    /// Implements algorithm X as a finite state machine
-    #[allow(dead_code)]
-    pub fn solve(&mut self) -> Option<Vec<String>> {
-        // Follows stages of algorithm description in Fasc 5c, Knuth
+        // A lot of already-forced (degree-1) items narrows the search
+        // quickly regardless of how dense the rest of the problem is, so
+        // it eases the classification by one tier
+        let eased = if degree_one_fraction >= 0.2 { 1.0 } else { 0.0 };
+        let score = avg_degree - eased;
 
-        // The only ways to break this loop are to yield a solution via X2 or to
-        // have exhausted all solutions via X8
-        loop {
-            match self.stage {
-                Stage::X2 => {
-                    if let Some(z) = self.x2() {
-                        return Some(z);
-                    }
-                }
-                Stage::X3 => {
-                    self.x3x4();
-                }
-                Stage::X5 => {
-                    self.x5();
-                }
-                Stage::X6 => {
-                    self.x6();
-                }
-                Stage::X8 => match self.x8() {
-                    true => {}
-                    false => {
-                        return None;
-                    }
-                },
-            };
+        match score {
+            s if s <= 1.0 => DifficultyClass::Trivial,
+            s if s <= 4.0 => DifficultyClass::Moderate,
+            s if s <= 7.0 => DifficultyClass::Hard,
+            _ => DifficultyClass::LikelyIntractable,
         }
     }
 
-    /// Returns a solution in a human-understandable form
-    ///
-    /// The solution vector `sol_vec` stores each of the OptionElements which
-    /// were used to cover the items in the solution.  To turn this into
-    /// something understandable we find the spacer to its right, and use this
-    /// with a lookup table created earlier to map this to the names of options
+    /// Returns the number of mandatory items this solver was constructed
+    /// with -- every item index below this one must be covered by a
+    /// solution, see [new_optional](Solver::new_optional)
+    /// ```
+    ///# use dlx_rs::solver::Solver;
     ///
-    // TODO: Is it useful to have the double map? We don't used spacer_ids for
-    //       anything else, so could condense it into a single HashMap
-    pub fn output(&self) -> Vec<String> {
-        let to_return = self
-            .sol_vec
-            .iter()
-            .take(self.l)
-            .map(|&x| self.spacer_for(x))
-            .map(|x| self.spacer_ids[&x])
-            .map(|x| self.names[x].clone())
-            .collect();
-        to_return
+    /// let s: Solver = Solver::new_optional(4, 1);
+    /// assert_eq!(s.num_mandatory(), 4);
+    /// assert_eq!(s.num_optional(), 1);
+    /// ```
+    pub fn num_mandatory(&self) -> Index {
+        self.optional - 1
     }
 
-    /// Stage X2 of Algorithm X
-    /// If rlink(0) = 0, then all items are covered, so return current solution
-    /// and also go to X8
-    fn x2(&mut self) -> Option<Vec<String>> {
-        //println!("State:");
-        //println!("{}",self);
-        //println!("RLINK: {}",self.elements[0].r());
-        if self.elements[0].r() == 0 || self.elements[0].r() >= self.optional {
-            if self.yielding {
-                self.yielding = false;
-                return Some(self.output());
-            } else {
-                self.yielding = true;
-                self.stage = Stage::X8;
-                return None;
-            }
-        }
-        self.stage = Stage::X3;
-        None
+    /// Returns the number of optional items this solver was constructed
+    /// with -- items that may be covered at most once, but never have to
+    /// be, see [new_optional](Solver::new_optional)
+    pub fn num_optional(&self) -> Index {
+        self.items - self.num_mandatory()
     }
 
-    /// Stages X3 and X4 of algorithm X
+    /// Returns every item still linked into the horizontal item list, i.e.
+    /// not yet covered by any chosen option
     ///
-    /// X3: Choose item `min_idx`, use MRV heuristic (i.e. smallest remaining value)
+    /// This walks the same list as [is_item_active](Solver::is_item_active),
+    /// but in one pass over every active item rather than one traversal per
+    /// query
+    /// ```
+    ///# use dlx_rs::solver::Solver;
     ///
-    /// X4: Cover item `min_idx`
-    fn x3x4(&mut self) -> Option<Vec<String>> {
-        // X3
-        // Heuristic we choose is MRV
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2]);
+    /// assert_eq!(s.uncovered_items(), vec![1, 2]);
+    /// ```
+    pub fn uncovered_items(&self) -> Vec<Index> {
+        let mut items = Vec::new();
+        let mut idx = self.elements[0].r();
+        while idx != 0 {
+            items.push(idx);
+            idx = self.elements[idx].r();
+        }
+        items
+    }
 
-        // Walk along items and find minimum l
+    /// Returns how many mandatory items are still uncovered, i.e. still
+    /// linked into the header row below [optional](Solver::new_optional)'s
+    /// threshold
+    ///
+    /// Useful as a progress indicator alongside [step](Solver::step), e.g.
+    /// in the step-through `interactive` example
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2]);
+    /// assert_eq!(s.remaining_mandatory(), 2);
+    /// s.step();
+    /// s.step();
+    /// assert_eq!(s.remaining_mandatory(), 1);
+    /// ```
+    pub fn remaining_mandatory(&self) -> usize {
         let mut idx = self.elements[0].r();
-        let mut min_idx = self.elements[0].r();
-        let mut min_l = self.elements[idx].get_l();
+        let mut remaining = 0;
         while idx != 0 && idx < self.optional {
-            let l = self.elements[idx].get_l();
-            if l < min_l {
-                min_l = l;
-                min_idx = idx;
-            }
+            remaining += 1;
             idx = self.elements[idx].r();
         }
+        remaining
+    }
 
-        // Now select the item which is covered by the minimum number of options
-        self.idx = min_idx;
-
-        // X4
-        // Cover i
-
-        //println!("Covering item X4: {}", self.idx);
-        self.cover(self.idx).unwrap();
+    /// Returns how many mandatory items have been covered so far, i.e. the
+    /// complement of [remaining_mandatory](Solver::remaining_mandatory)
+    /// against the total number of mandatory items
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2]);
+    /// assert_eq!(s.covered_count(), 0);
+    /// s.step();
+    /// s.step();
+    /// assert_eq!(s.covered_count(), 1);
+    /// ```
+    pub fn covered_count(&self) -> usize {
+        (self.optional - 1) - self.remaining_mandatory()
+    }
 
-        // Set x_l <- DLINK(i)
-        let x_l = self.elements[self.idx].d();
+    /// Attaches a human-readable name to each item, in order starting from
+    /// item `1`. Fewer names than items may be given; the remaining items
+    /// keep their numeric indices.
+    ///
+    /// This only affects presentation: [Display](fmt::Display) prints
+    /// item names where set, falling back to the raw index otherwise, and
+    /// [item_name](Solver::item_name) returns them back by index.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.with_item_names(&["a", "b"]);
+    ///
+    /// assert_eq!(s.item_name(1), Some("a"));
+    /// assert_eq!(s.item_name(2), Some("b"));
+    /// ```
+    pub fn with_item_names(&mut self, names: &[&str]) -> &mut Self {
+        for (i, name) in names.iter().enumerate() {
+            if let Some(slot) = self.item_names.get_mut(i + 1) {
+                *slot = Some(name.to_string());
+            }
+        }
+        self
+    }
 
-        // Save x_l in current guesses
-        //     println!("self.l: {}",self.l);
-        self.sol_vec[self.l] = x_l;
+    /// Returns the name given to item `i` via
+    /// [with_item_names](Solver::with_item_names), or `None` if it was
+    /// never named
+    pub fn item_name(&self, i: Index) -> Option<&str> {
+        self.item_names.get(i)?.as_deref()
+    }
 
-        self.stage = Stage::X5;
-        None
+    /// Label used when rendering item `i`: its name if set, otherwise the
+    /// raw index
+    fn item_label(&self, i: Index) -> String {
+        match self.item_name(i) {
+            Some(name) => name.to_string(),
+            None => i.to_string(),
+        }
     }
 
-    /// Stages X5 and X7 of Algorithm X
+    /// Renders the current dancing-links state as a Graphviz `dot`
+    /// bipartite graph, with an edge `item -> option` for every
+    /// still-active item and every option currently covering it
     ///
-    /// Try x_l
+    /// This is the same information as the [Display] grid, in a form
+    /// that can be piped through `dot` to visualise how the matrix
+    /// shrinks after each [step](Solver::step)
+    /// ```
+    ///# use dlx_rs::solver::Solver;
     ///
-    /// If x_l = i, then we are out of options and execute X7: backtrack
+    /// let mut s: Solver = Solver::new(1);
+    /// s.add_option("o1", &[1]);
+    /// assert!(s.to_dot().starts_with("digraph dlx {\n"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph dlx {\n");
+        for item in self.uncovered_items() {
+            dot += &format!(
+                "  \"i{}\" [label=\"{}\", shape=box];\n",
+                item,
+                self.item_label(item)
+            );
+            let mut p = self.elements[item].d();
+            while p != item {
+                let spacer = self.spacer_for(p);
+                let name = &self.names[self.spacer_ids[&spacer]];
+                dot += &format!("  \"i{}\" -> \"{}\";\n", item, name);
+                p = self.elements[p].d();
+            }
+        }
+        dot += "}\n";
+        dot
+    }
+
+    /// Renders this solver's exact-cover matrix as a LaTeX `tabular`: a
+    /// header row of item labels, then one row per option (in insertion
+    /// order) with `1` where the option covers the item and blank
+    /// otherwise -- a LaTeX counterpart to the ASCII matrix in the
+    /// crate's README, suitable for dropping straight into a paper
     ///
-    /// Otherwise, cover all other items in option x_l, increase level and go back to X2
+    /// Unlike [Display] (which only shows the *current*, possibly
+    /// partially-covered state), this renders the original full matrix
+    /// from `self.options`/`self.names`, unaffected by search progress. A
+    /// vertical rule in the column spec separates mandatory items from
+    /// [optional](Solver::new_optional) ones, if there are any.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
     ///
-    fn x5(&mut self) -> Option<Vec<String>> {
-        // X5
-        // Try x_l
-        // If x_l = i, then we are out of options and go to X7
-        // Otherwise, cover all other items in option x_l, increase level and go back to X2
-        //        println!("Partial sol: {:?}", &self.sol_vec[..self.l]);
+    /// let mut s: Solver = Solver::new(4);
+    /// s.add_option("o1", &[1, 3])
+    ///     .add_option("o2", &[2, 4])
+    ///     .add_option("o3", &[1, 2]);
+    ///
+    /// let latex = s.export_latex();
+    /// assert!(latex.starts_with("\\begin{tabular}{lcccc}\n"));
+    /// assert!(latex.contains("o1 & 1 &  & 1 &  \\\\\n"));
+    /// assert!(latex.ends_with("\\end{tabular}\n"));
+    /// ```
+    pub fn export_latex(&self) -> String {
+        let mandatory = self.optional - 1;
+        let mut col_spec = String::from("l");
+        for i in 1..=self.items {
+            if i == mandatory + 1 {
+                col_spec.push('|');
+            }
+            col_spec.push('c');
+        }
 
-        // Try xl
-        let x_l = self.sol_vec[self.l];
-        //        println!("Trying x_{}= {}", self.l, x_l);
-        //        println!("idx: {}", self.idx);
+        let mut latex = format!("\\begin{{tabular}}{{{col_spec}}}\n");
 
-        // If out of options (x_l reads downwards from self.idx, so have looped back around), backtrack
-        if x_l == self.idx {
-            // X7
-            // Backtrack: Uncover item (i)
+        let header: String = (1..=self.items)
+            .map(|i| self.item_label(i))
+            .collect::<Vec<_>>()
+            .join(" & ");
+        latex += &format!(" & {header} \\\\\n\\hline\n");
 
-            //            println!("Uncovering X7: {}", x_l);
-            self.uncover(x_l).unwrap();
-            self.stage = Stage::X8;
-            return None;
+        let mut option_items: Vec<Vec<Index>> = vec![Vec::new(); self.names.len()];
+        for (&spacer, &name_id) in &self.spacer_ids {
+            option_items[name_id] = self.options[&spacer].iter().map(|&(item, _)| item).collect();
         }
 
-        let mut p = x_l + 1;
-        while p != x_l {
-            //            println!("p: {}", p);
-
-            match &self.elements[p] {
-                Link::Spacer(_) => {
-                    // If a spacer, then hop up one link
-                    p = self.elements[p].u();
-                }
-                op @ Link::OptionElement(_) => {
-                    //                    println!("Covering X5: {}", j);
-                    //                    println!("State:");
-                    //                    println!("{}", self);
-                    let j = op.top();
-
-                    self.cover(j).unwrap();
-                }
-                Link::Item(x) => {
-                    panic!("Trying an item {:?}", x);
-                }
-            };
-            p += 1;
+        for (name, items) in self.names.iter().zip(option_items.iter()) {
+            let row: String = (1..=self.items)
+                .map(|i| if items.contains(&i) { "1" } else { "" })
+                .collect::<Vec<_>>()
+                .join(" & ");
+            latex += &format!("{name} & {row} \\\\\n");
         }
-        //        println!("--");
 
-        self.l += 1;
-        self.stage = Stage::X2;
-        None
+        latex += "\\end{tabular}\n";
+        latex
     }
 
-    /// Stage X6 of Algorithm X
+    /// Turns on an invariant check: every solution yielded from now on is
+    /// recorded, and if the same option set is ever produced twice,
+    /// [saw_duplicate](Solver::saw_duplicate) will report `true`
     ///
-    /// Try again
+    /// A correct exact-cover enumeration never yields the same solution
+    /// twice, so this is a cheap way to guard against a link-management
+    /// regression while testing, without paying the cost in normal use
     ///
-    /// Uncover items != i in option x_l, then set x_l = DLINK(x_l): this is how we move through all of the options
-    fn x6(&mut self) -> Option<Vec<String>> {
-        let x_l = self.sol_vec[self.l];
-        let mut p = x_l - 1;
-
-        while p != x_l {
-            let j = self.elements[p].top();
-            if j == 0 {
-                p = self.elements[p].d();
-            } else {
-                //                println!("Uncovering X6: {}",j);
-                self.uncover(j).unwrap();
-            }
-            p -= 1;
-        }
-        self.idx = self.elements[x_l].top();
-        self.sol_vec[self.l] = self.elements[x_l].d();
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[1])
+    ///     .add_option("o3", &[2, 3]);
+    ///
+    /// s.enable_dup_detection();
+    /// let sols: Vec<Vec<String>> = s.by_ref().collect();
+    /// assert_eq!(sols.len(), 2);
+    /// assert!(!s.saw_duplicate());
+    /// ```
+    pub fn enable_dup_detection(&mut self) {
+        self.dup_detection = true;
+    }
 
-        self.stage = Stage::X5;
-        None
+    /// Prunes symmetric duplicate solutions during search, instead of
+    /// enumerating every solution and deduplicating afterwards
+    ///
+    /// `canon` maps a completed solution's option indices (in
+    /// [output_indices](Solver::output_indices) order, read right when the
+    /// solution is found) to a canonical signature -- e.g. the smallest
+    /// hash over all of the problem's symmetries (rotations/reflections for
+    /// [Queens](crate::queens::Queens)'s board). Once a signature has been
+    /// seen, any later solution hashing to it is dropped before
+    /// [output](Solver::output) even allocates a `Vec<String>` for it, and
+    /// the search resumes from there instead of returning it -- cheaper
+    /// than collecting every solution and deduplicating by name afterwards,
+    /// though each duplicate still has to be reached in full before it's
+    /// recognised as one: `canon` only ever sees a *complete* solution, not
+    /// a partial one, since symmetry is a property of the whole placement
+    /// rather than of any prefix of it.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// // o1 and o2 are each other's mirror image, so only one of the two
+    /// // otherwise-identical solutions should come out once pruning is on
+    /// let mut s: Solver = Solver::new(1);
+    /// s.add_option("o1", &[1]).add_option("o2", &[1]);
+    /// assert_eq!(s.clone().count(), 2);
+    ///
+    /// s.with_symmetry_pruner(|picks| picks.iter().map(|&i| i.min(1 - i)).sum::<usize>() as u64);
+    /// assert_eq!(s.count(), 1);
+    /// ```
+    pub fn with_symmetry_pruner(
+        &mut self,
+        canon: impl Fn(&[Index]) -> u64 + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.symmetry_pruner = Some(Arc::new(canon));
+        self
     }
 
-    /// Stage X8 of Algorithm X
-    /// Leave level l
-    /// Terminate if l=0, otherwise l=l-1, go to X6
-    fn x8(&mut self) -> bool {
-        // X8
-        match self.l {
-            0 => false,
-            _ => {
-                self.l -= 1;
-                self.stage = Stage::X6;
-                true
-            }
-        }
+    /// Shrinks the solver's internal vectors and maps to fit their current
+    /// contents, reclaiming any excess capacity left over from building up
+    /// the problem with repeated [add_option](Solver::add_option) calls
+    ///
+    /// Call this once all options have been added and before iterating --
+    /// a long-running search on a large grid gains nothing from capacity
+    /// trimmed mid-search, and this method doesn't touch anything the
+    /// search touches (`l`, `stage`, `started`, ...), only the
+    /// already-built option/item structure.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2]);
+    /// s.shrink_to_fit();
+    /// assert_eq!(s.count(), 1);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.elements.shrink_to_fit();
+        self.sol_vec.shrink_to_fit();
+        self.names.shrink_to_fit();
+        self.item_names.shrink_to_fit();
+        self.meta.shrink_to_fit();
+        self.options.shrink_to_fit();
+        self.spacer_ids.shrink_to_fit();
+        self.seen_solutions.shrink_to_fit();
+        self.seen_signatures.shrink_to_fit();
     }
 
-    /// Takes in a non-item node and steps rightwards along `self.elements` the
-    /// until a spacer is found, upon which the index is returned
-    fn spacer_for(&self, x: Index) -> Index {
-        let mut p = x;
-        loop {
-            match self.elements[p] {
-                Link::Spacer(_) => return p,
-                Link::OptionElement(_) => p += 1,
-                Link::Item(_) => panic!("Somehow ended up on an item"),
-            };
-        }
+    /// Controls whether optional items (see [new_optional](Solver::new_optional))
+    /// may influence the X3 MRV heuristic's tie-breaking
+    ///
+    /// The item actually branched on is still always a mandatory one --
+    /// covering is not required for an optional item, so branching
+    /// directly on one the way X4 covers a mandatory item would wrongly
+    /// force it to be covered by one of its options, discarding the
+    /// (equally valid) choice of leaving it uncovered.
+    ///
+    /// Off by default: ties among mandatory items with the same minimal
+    /// number of covering options are broken by picking whichever is
+    /// found first. Turning this on breaks such ties in favour of the
+    /// mandatory item that shares a row with the most tightly-constrained
+    /// optional item, on the theory that branching there is more likely
+    /// to prune a heavily-constrained part of the search next. This can
+    /// help on problems with many optional items, like
+    /// [Queens](crate::queens::Queens)'s diagonal/square items -- worth
+    /// benchmarking both settings on the problem at hand.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new_optional(1, 1);
+    /// s.add_option("o1", &[1]).add_option("o2", &[1, 2]);
+    /// s.include_optional_in_mrv(true);
+    /// assert_eq!(s.count(), 2);
+    /// ```
+    pub fn include_optional_in_mrv(&mut self, on: bool) {
+        self.include_optional_in_mrv = on;
     }
 
-    /// Selects an option with the name `name` When setting up a general
-    /// constraint solution, this is how to search for specific answers e.g. a
-    /// Sudoku has all the constraints (items and options), and then the squares
-    /// filled out in the specific problem need to be selected
+    /// Sets the direction [x3x4](Solver::x3x4)/[x6](Solver::x6) walk each
+    /// item's column when picking and advancing through its options
     ///
-    /// So for the problem
+    /// [Natural](Traversal::Natural) (the default) tries options in the
+    /// order they were added; [Reverse](Traversal::Reverse) tries them in
+    /// the opposite order. This only changes the order solutions are
+    /// found in -- the set of solutions, and in particular
+    /// [count](Iterator::count), is unaffected.
     ///
-    /// ```text
-    ///    i1  i2  i3
-    /// o1  1   0   0
-    /// o2  1   0   0
-    /// o3  0   1   1
     /// ```
-    /// Clearly *both* \[o1,o3\] and \[o2,o3\] are solutions, but if we select o1, then only one solution remains
+    ///# use dlx_rs::solver::{Solver, Traversal};
     ///
+    /// let mut natural: Solver = Solver::new(1);
+    /// natural.add_option("o1", &[1]).add_option("o2", &[1]);
+    /// assert_eq!(natural.next(), Some(vec!["o1".to_string()]));
+    ///
+    /// let mut reversed: Solver = Solver::new(1);
+    /// reversed.add_option("o1", &[1]).add_option("o2", &[1]);
+    /// reversed.set_traversal(Traversal::Reverse);
+    /// assert_eq!(reversed.next(), Some(vec!["o2".to_string()]));
     /// ```
-    ///# use dlx_rs::solver::Solver;
+    pub fn set_traversal(&mut self, traversal: Traversal) {
+        self.traversal = traversal;
+    }
+
+    /// Sets which item X3 branches on first, see [Heuristic]
     ///
-    /// let mut s = Solver::new(3);
+    /// [Mrv](Heuristic::Mrv) (the default) picks the mandatory item with
+    /// the fewest remaining covering options; [FirstFit](Heuristic::FirstFit)
+    /// always picks whichever uncovered mandatory item comes first instead,
+    /// for a predictable baseline when benchmarking or cross-validating
+    /// against a reference implementation that doesn't use MRV. Either way
+    /// the set of solutions found -- and in particular
+    /// [count](Iterator::count) -- is unaffected; only the branching order,
+    /// and therefore how much work the search does to get there, changes.
+    /// ```
+    ///# use dlx_rs::solver::{Solver, Heuristic};
     ///
-    /// s.add_option("o1", &[1])
-    ///     .add_option("o2", &[1])
-    ///     .add_option("o3", &[2, 3]);
+    /// // Item 1 has two covering options, item 2 only one -- MRV prefers
+    /// // the more tightly-constrained item 2, finding y1 before x1
+    /// let mut by_mrv: Solver = Solver::new(2);
+    /// by_mrv.add_option("x1", &[1]).add_option("x2", &[1]).add_option("y1", &[2]);
+    /// assert_eq!(by_mrv.next(), Some(vec!["y1".to_string(), "x1".to_string()]));
     ///
-    /// // First get all solutions
-    /// let sols: Vec<Vec<String>> = s.clone().collect();
-    /// assert_eq!( sols.len(), 2);
-    /// assert_eq!( vec!["o3", "o1"], sols[0]);
-    /// assert_eq!( vec!["o3", "o2"], sols[1]);
+    /// // FirstFit ignores that and always branches on item 1 first instead
+    /// let mut first_fit: Solver = Solver::new(2);
+    /// first_fit.add_option("x1", &[1]).add_option("x2", &[1]).add_option("y1", &[2]);
+    /// first_fit.set_heuristic(Heuristic::FirstFit);
+    /// assert_eq!(first_fit.next(), Some(vec!["x1".to_string(), "y1".to_string()]));
+    /// ```
+    pub fn set_heuristic(&mut self, heuristic: Heuristic) {
+        self.heuristic = heuristic;
+    }
+
+    /// Overrides [x3x4](Solver::x3x4)'s MRV heuristic, forcing it to branch
+    /// on items in exactly the given sequence instead of picking whichever
+    /// is covered by the fewest options
     ///
+    /// Items already covered (or not yet reached) are skipped in order, so
+    /// `order` need not be updated as the search progresses; it is read
+    /// once per X3 step. Optional items are never branched on -- if
+    /// `order` names one, it is skipped just like an already-covered item,
+    /// the same restriction [include_optional_in_mrv](Solver::include_optional_in_mrv)
+    /// describes for MRV tie-breaking. If every item in `order` has
+    /// already been covered or is optional, X3 falls back to MRV so the
+    /// search can still make progress.
     ///
-    /// // Now select o1 and get all solutions
-    /// s.select("o1");
-    /// assert_eq!( vec!["o3"], s.next().unwrap());
+    /// This only changes the order solutions are discovered in -- the set
+    /// of solutions, and in particular [count](Iterator::count), is
+    /// unaffected.
     /// ```
-    pub fn select(&mut self, name: &str) -> Result<(), &'static str> {
-        // This selects an option by doing the followings
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut by_mrv: Solver = Solver::new(2);
+    /// by_mrv.add_option("x1", &[1])
+    ///     .add_option("x2", &[1, 2])
+    ///     .add_option("y1", &[2]);
+    /// // Items 1 and 2 are tied at 2 covering options each, so default MRV
+    /// // breaks the tie by branching on item 1 first, finding x1+y1
+    /// assert_eq!(by_mrv.next(), Some(vec!["x1".to_string(), "y1".to_string()]));
+    ///
+    /// let mut ordered: Solver = Solver::new(2);
+    /// ordered.add_option("x1", &[1])
+    ///     .add_option("x2", &[1, 2])
+    ///     .add_option("y1", &[2]);
+    /// // Forcing item 2 to be branched on first instead finds x2 (which
+    /// // covers both items at once) as the first solution
+    /// ordered.set_item_order(&[2, 1]);
+    /// assert_eq!(ordered.next(), Some(vec!["x2".to_string()]));
+    /// ```
+    pub fn set_item_order(&mut self, order: &[Index]) {
+        self.item_order = Some(order.to_vec());
+    }
 
-        // First get the spacer position of the option by firstly finding which
-        // option it was
-        let id = match self
-            .names
-            .clone()
+    /// Caps `elements` (the internal dancing-links node storage) at `max`
+    /// entries; once set, [add_option_checked](Solver::add_option_checked)
+    /// refuses to grow past it instead of allocating unboundedly
+    ///
+    /// Unset (unlimited) by default, preserving the existing behaviour of
+    /// [add_option](Solver::add_option). A guard against accidentally huge
+    /// problem sizes -- e.g. a typo'd 100x100 Sudoku -- built from an
+    /// untrusted or user-supplied size parameter.
+    pub fn set_node_limit(&mut self, max: usize) {
+        self.node_limit = Some(max);
+    }
+
+    /// Returns whether [enable_dup_detection](Solver::enable_dup_detection)
+    /// has observed the same solution yielded more than once
+    pub fn saw_duplicate(&self) -> bool {
+        self.saw_duplicate
+    }
+
+    /// Returns the internal error that halted the search, if
+    /// [cover](Solver::cover)/[uncover](Solver::uncover) ever failed
+    /// mid-search
+    ///
+    /// This should never be `Some` in practice -- it would mean a
+    /// dancing-links invariant was violated -- but [solve](Solver::solve),
+    /// [step](Solver::step) and [next](Iterator::next) check for it and
+    /// halt the search gracefully instead of panicking, preserving the
+    /// error here for diagnosis rather than losing it.
+    pub fn last_error(&self) -> Option<&SolverError> {
+        self.last_error.as_ref()
+    }
+
+    /// Records `msg` as the reason the search halted, so subsequent
+    /// [solve](Solver::solve)/[step](Solver::step) calls stop immediately
+    /// instead of continuing from a now-inconsistent state
+    fn fail(&mut self, msg: &'static str) {
+        self.last_error = Some(SolverError::Internal(msg.to_string()));
+    }
+
+    /// Records a [SearchEvent] for [events](Solver::events), if an
+    /// [events](Solver::events) iterator is currently live -- a no-op
+    /// otherwise, so [solve](Solver::solve)/[step](Solver::step) pay nothing
+    /// for this when nobody is watching
+    fn push_event(&mut self, ev: SearchEvent) {
+        if let Some(queue) = self.event_queue.as_mut() {
+            queue.push_back(ev);
+        }
+    }
+
+    /// Returns a snapshot of every element's `(u, d, l, r)` links
+    ///
+    /// This exposes exactly enough of the internal linked-list structure
+    /// to let callers assert the core dancing-links invariant: covering an
+    /// item and then uncovering it must restore the structure exactly, as
+    /// demonstrated in the `cover_uncover_identity` test below. Only
+    /// available under `cfg(test)` or the `testing` feature, since it's a
+    /// correctness-testing hook rather than part of the normal API.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn snapshot_elements(&self) -> Vec<(Index, Index, Index, Index)> {
+        self.elements
             .iter()
-            .position(|x| x == &name.to_string())
-        {
-            Some(z) => z,
-            None => return Err("Invalid option specified"),
+            .map(|e| (e.u(), e.d(), e.l(), e.r()))
+            .collect()
+    }
+
+    /// Covers item in column `i`
+    /// i.e. `cover(2)` would transform
+    ///
+    /// ```text
+    /// i0  ⟷  i1  ⟷  i2  ⟷  i3  ⟷  i4
+    ///        ⥯      ⥯     ⥯     ⥯   s0
+    /// o1     ⦿      ⦿     ⥯     ⥯   s1
+    /// o2     ⥯      ⥯     ⦿     ⥯   s2
+    /// o3     ⥯      ⦿     ⥯     ⦿   s3
+    /// o4     ⦿      ⥯     ⥯     ⥯   s4
+    ///        ⥯      ⥯     ⥯     ⥯
+    /// ```
+    /// into
+    ///
+    /// ```text
+    /// i0  ⟷  i1  ⟷  ⟷  ⟷  i3  ⟷  i4
+    ///        ⥯            ⥯     ⥯   s0
+    /// o1     ⦿            ⥯     ⥯   s1
+    /// o2     ⥯            ⦿     ⥯   s2
+    /// o3     ⥯            ⥯     ⦿   s3
+    /// o4     ⦿            ⥯     ⥯   s4
+    ///        ⥯            ⥯     ⥯
+    /// ```
+    pub fn cover(&mut self, i: Index) -> Result<(), &'static str> {
+        let col = &mut self.elements[i];
+        match col {
+            Link::Item(_) => {}
+            _ => return Err("Can only cover items"),
         };
-        /*
-        let mut id =0;
-        for (i,item) in self.names.iter().enumerate() {
-            if *item == name.to_string() {
-                id = i;
-                break;
-            }
+        // Hide all of the options in col i
+        let mut p = col.d();
+        while p != i {
+            self.hide(p)?;
+            p = self.elements[p].d();
         }
-        */
-        // Now find the spacer id by going this many links down the chain
-        // Start at root spacer node
-        let mut spacer_id = self.items + 1;
-        for _ in 0..id {
-            spacer_id = self.elements[spacer_id].d();
+
+        // Unlink item
+        self.unlink_item(i);
+        //let l = self.elements[i].l();
+        //let r = self.elements[i].r();
+        //self.elements[l].set_r(r);
+        //self.elements[r].set_l(l);
+
+        Ok(())
+    }
+
+    /// Like [cover](Solver::cover), but returns a [CoverGuard] that
+    /// automatically [uncover](Solver::uncover)s item `i` when dropped,
+    /// instead of requiring the caller to pair the calls up by hand
+    ///
+    /// Safer for manual what-if exploration than the raw `cover`/`uncover`
+    /// pair: an early return or a `?` between them would otherwise leave
+    /// the item covered and corrupt the solver's state for good, whereas
+    /// the guard restores it regardless of how its scope is left.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2]);
+    ///
+    /// let before = s.uncovered_items();
+    /// {
+    ///     let guard = s.cover_scoped(1).unwrap();
+    ///     assert_eq!(guard.uncovered_items(), vec![2]);
+    /// }
+    /// assert_eq!(s.uncovered_items(), before);
+    /// ```
+    pub fn cover_scoped(&mut self, i: Index) -> Result<CoverGuard<'_, M>, SolverError> {
+        if i == 0 || i >= self.elements.len() || !matches!(self.elements[i], Link::Item(_)) {
+            return Err(SolverError::ItemOutOfRange(i));
         }
-        //        println!("Spacer id: {}", spacer_id);
+        self.cover(i)
+            .map_err(|msg| SolverError::Internal(msg.to_string()))?;
+        Ok(CoverGuard {
+            solver: self,
+            item: i,
+        })
+    }
 
-        // Now have the spacer node: cycle around and hide everything until we are at the next spacer mode
-        let mut p = spacer_id + 1;
+    /// Unlinks an item from the horizontally linked list
+    fn unlink_item(&mut self, i: Index) {
+        let l = self.elements[i].l();
+        let r = self.elements[i].r();
+        self.elements[l].set_r(r);
+        self.elements[r].set_l(l);
+    }
 
-        loop {
-            match self.elements[p] {
+    /// Relinks an item into the horizontally linked list
+    ///
+    /// Must be done in the reverse order to unlinking
+    fn relink_item(&mut self, i: Index) {
+        let l = self.elements[i].l();
+        let r = self.elements[i].r();
+        self.elements[l].set_r(i);
+        self.elements[r].set_l(i);
+    }
+
+    /// When selecting an option, this runs through all of the items it covers
+    /// and unlinks those OptionElements vertically
+    fn hide(&mut self, p: Index) -> Result<(), &'static str> {
+        let mut q = p + 1;
+        while q != p {
+            let x = self.elements[q].top();
+            let u = self.elements[q].u();
+            let d = self.elements[q].d();
+
+            match self.elements[q] {
+                Link::Item(_) => return Err("Hide encountered and item"),
+                Link::Spacer(_) => q = u,
                 Link::OptionElement(_) => {
-                    self.cover(self.elements[p].top()).unwrap();
-                    p += 1;
+                    self.elements[u].set_d(d);
+                    self.elements[d].set_u(u);
+                    self.elements[x].dec_l();
                 }
-                Link::Spacer(_) => break,
-                Link::Item(_) => break,
             };
+            q += 1;
         }
 
         Ok(())
     }
-}
 
-impl Iterator for Solver {
-    type Item = Vec<String>;
-    /// Produces next solution by following algorithm X
-    /// as described in tAoCP in Fasc 5c, Dancing Links, Knuth
-    ///
-    /// Returns `Some` containing a vector of items if a solution remains, or
-    /// `None` when no more solutions remaining
-    fn next(&mut self) -> Option<Self::Item> {
-        self.solve()
-    }
-}
+    /// Reverse of function [cover](crate::solver::Solver::cover)
+    pub fn uncover(&mut self, i: Index) -> Result<(), &'static str> {
+        // Relink item
+        self.relink_item(i);
+        //let l = self.elements[i].l();
+        //let r = self.elements[i].r();
+        //self.elements[l].set_r(i);
+        //self.elements[r].set_l(i);
 
-#[cfg(test)]
-mod tests {
+        let col = &mut self.elements[i];
 
-    use super::*;
+        match col {
+            Link::Item(_) => {}
+            _ => return Err("Can only uncover items"),
+        };
 
-    #[test]
-    fn spacer_for() {
-        let mut s = Solver::new(4);
-        s.add_option("o1", &[1, 2])
-            .add_option("o2", &[2, 3])
-            .add_option("o3", &[3, 4])
-            .add_option("o4", &[1, 4]);
+        // Hide all of the options in col i
+        let mut p = col.u();
+        while p != i {
+            self.unhide(p)?;
+            p = self.elements[p].u();
+        }
 
-        // This creates a vec which looks like
-        // [i0, i1, i2, i3, i4, s0
-        //      x    x          s1
-        //           x   x      s2
-        //               x   x  s3
-        //      x            x  s4]
-        //
+        Ok(())
+    }
 
-        let spacer_answers = HashMap::from([
-            (6, 8),
-            (7, 8),
-            (8, 8),
+    /// Reverse of function [hide](crate::solver::Solver::hide)
+    fn unhide(&mut self, p: Index) -> Result<(), &'static str> {
+        let mut q = p - 1;
+        while q != p {
+            let x = self.elements[q].top();
+            let u = self.elements[q].u();
+            let d = self.elements[q].d();
+
+            match self.elements[q] {
+                Link::Item(_) => return Err("Hide encountered and item"),
+                Link::Spacer(_) => q = d,
+                Link::OptionElement(_) => {
+                    self.elements[u].set_d(q);
+                    self.elements[d].set_u(q);
+                    self.elements[x].inc_l();
+                }
+            };
+            q -= 1;
+        }
+
+        Ok(())
+    }
+
+    /// Removes every node from colored item `item`'s column whose color
+    /// doesn't match `color`, along with the rest of each such node's row
+    /// (exactly as [cover](Solver::cover) would for an uncolored item),
+    /// returning the removed nodes in removal order so [unpurify](Solver::unpurify)
+    /// can restore them
+    ///
+    /// Unlike `cover`, `item` itself is never unlinked from the item list
+    /// (a colored item stays available for other options to add compatible
+    /// rows to), and only the non-matching nodes are spliced out of its own
+    /// column -- matching nodes are left exactly as they were
+    fn purify(&mut self, item: Index, color: u32) -> Result<Vec<Index>, &'static str> {
+        let mut removed = Vec::new();
+        let mut q = self.elements[item].d();
+        while q != item {
+            let next = self.elements[q].d();
+            if !matches!(self.elements[q], Link::OptionElement(_)) {
+                return Err("purify encountered a non-option-element node in a colored column");
+            }
+            if self.elements[q].color() != Some(color) {
+                let u = self.elements[q].u();
+                let d = self.elements[q].d();
+                self.elements[u].set_d(d);
+                self.elements[d].set_u(u);
+                self.elements[item].dec_l();
+                self.hide(q)?;
+                removed.push(q);
+            }
+            q = next;
+        }
+        Ok(removed)
+    }
+
+    /// Reverse of [purify](Solver::purify): re-splices `removed` back into
+    /// `item`'s column and [unhide](Solver::unhide)s each of their rows,
+    /// in the opposite order they were removed in
+    fn unpurify(&mut self, item: Index, removed: &[Index]) -> Result<(), &'static str> {
+        for &q in removed.iter().rev() {
+            self.unhide(q)?;
+            let u = self.elements[q].u();
+            let d = self.elements[q].d();
+            self.elements[u].set_d(q);
+            self.elements[d].set_u(q);
+            self.elements[item].inc_l();
+        }
+        Ok(())
+    }
+
+    /// Applies row node `p` the way [x5](Solver::x5) applies every "other"
+    /// item in a newly-chosen row: an ordinary node covers its item exactly
+    /// as before, while a [colored](Solver::add_option_colored) node commits
+    /// its item to that color, [purify](Solver::purify)ing away incompatible
+    /// rows the first time the item is committed at this `depth` and
+    /// becoming a no-op for later rows that agree on the same color
+    fn commit(&mut self, p: Index, depth: usize) -> Result<(), &'static str> {
+        let item = self.elements[p].top();
+        let color = match self.elements[p].color() {
+            Some(color) => color,
+            None => return self.cover(item),
+        };
+        match self.committed_colors.get(&item).copied() {
+            None => {
+                let removed = self.purify(item, color)?;
+                self.committed_colors.insert(item, color);
+                self.committed_depth.insert(item, depth);
+                self.purify_log.insert(item, removed);
+                Ok(())
+            }
+            Some(existing) if existing == color => Ok(()),
+            Some(_) => Err("colored item committed to conflicting colors"),
+        }
+    }
+
+    /// Reverse of [commit](Solver::commit): only the row/depth that
+    /// originally committed a colored item actually reverses its
+    /// [purify](Solver::purify) -- a later row that merely agreed with an
+    /// already-committed color has nothing of its own to undo
+    fn uncommit(&mut self, p: Index, depth: usize) -> Result<(), &'static str> {
+        let item = self.elements[p].top();
+        if self.elements[p].color().is_none() {
+            return self.uncover(item);
+        }
+        if self.committed_depth.get(&item) == Some(&depth) {
+            let removed = self.purify_log.remove(&item).unwrap_or_default();
+            self.unpurify(item, &removed)?;
+            self.committed_colors.remove(&item);
+            self.committed_depth.remove(&item);
+        }
+        Ok(())
+    }
+
+    /// Implements algorithm X as a finite state machine
+    #[allow(dead_code)]
+    pub fn solve(&mut self) -> Option<Vec<String>> {
+        // Follows stages of algorithm description in Fasc 5c, Knuth
+        self.started = true;
+        if self.last_error.is_some() {
+            return None;
+        }
+
+        // The only ways to break this loop are to yield a solution via X2,
+        // to have exhausted all solutions via X8, or to hit an internal
+        // error recorded via self.fail (see last_error)
+        loop {
+            match self.stage {
+                Stage::X2 => {
+                    if let Some(z) = self.x2() {
+                        return Some(z);
+                    }
+                }
+                Stage::X3 => {
+                    self.x3x4();
+                }
+                Stage::X5 => {
+                    self.x5();
+                }
+                Stage::X6 => {
+                    self.x6();
+                }
+                Stage::X8 => match self.x8() {
+                    true => {}
+                    false => {
+                        return None;
+                    }
+                },
+            };
+            if self.last_error.is_some() {
+                return None;
+            }
+        }
+    }
+
+    /// Advances the Algorithm X state machine by exactly one stage (X2,
+    /// X3/X4, X5, X6 or X8), instead of looping through stages until a
+    /// solution is yielded or the search is exhausted like [solve](Solver::solve)
+    ///
+    /// Meant for interactive/visualising callers that want to show the
+    /// dancing-links structure changing one transition at a time, e.g. by
+    /// printing [current_partial](Solver::current_partial),
+    /// [uncovered_items](Solver::uncovered_items), or the [Display] grid
+    /// between calls
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///# use dlx_rs::solver::StepOutcome;
+    ///
+    /// let mut s: Solver = Solver::new(1);
+    /// s.add_option("o1", &[1]);
+    ///
+    /// let mut last = StepOutcome::Continue;
+    /// while matches!(last, StepOutcome::Continue) {
+    ///     last = s.step();
+    /// }
+    /// assert_eq!(last, StepOutcome::Solution(vec!["o1".to_string()]));
+    /// ```
+    pub fn step(&mut self) -> StepOutcome {
+        self.started = true;
+        if self.last_error.is_some() {
+            return StepOutcome::Exhausted;
+        }
+        let outcome = match self.stage {
+            Stage::X2 => match self.x2() {
+                Some(sol) => StepOutcome::Solution(sol),
+                None => StepOutcome::Continue,
+            },
+            Stage::X3 => {
+                self.x3x4();
+                StepOutcome::Continue
+            }
+            Stage::X5 => {
+                self.x5();
+                StepOutcome::Continue
+            }
+            Stage::X6 => {
+                self.x6();
+                StepOutcome::Continue
+            }
+            Stage::X8 => {
+                if self.x8() {
+                    StepOutcome::Continue
+                } else {
+                    StepOutcome::Exhausted
+                }
+            }
+        };
+        if self.last_error.is_some() {
+            StepOutcome::Exhausted
+        } else {
+            outcome
+        }
+    }
+
+    /// Like [solve](Solver::solve), but notifies `obs` of [Observer] events
+    /// (a solution found, a backtrack taken) as the state machine runs,
+    /// without the caller having to drive [step](Solver::step) by hand just
+    /// to get visibility into progress
+    ///
+    /// Useful for attaching a progress counter or live display to a long
+    /// enumeration (see [Queens](crate::queens::Queens),
+    /// [Aztec](crate::aztec::Aztec)) while still getting solutions back one
+    /// at a time, the same way [solve](Solver::solve) does.
+    /// ```
+    ///# use dlx_rs::solver::{Solver, Observer};
+    ///
+    /// #[derive(Default)]
+    /// struct Counter { solutions: usize, backtracks: usize }
+    /// impl Observer for Counter {
+    ///     fn on_solution(&mut self, _depth: usize) { self.solutions += 1; }
+    ///     fn on_backtrack(&mut self) { self.backtracks += 1; }
+    /// }
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2]);
+    ///
+    /// let mut counter = Counter::default();
+    /// while s.solve_observed(&mut counter).is_some() {}
+    /// assert_eq!(counter.solutions, 1);
+    /// ```
+    pub fn solve_observed(&mut self, obs: &mut impl Observer) -> Option<Vec<String>> {
+        self.started = true;
+        if self.last_error.is_some() {
+            return None;
+        }
+
+        loop {
+            match self.stage {
+                Stage::X2 => {
+                    if let Some(z) = self.x2() {
+                        obs.on_solution(self.l);
+                        return Some(z);
+                    }
+                }
+                Stage::X3 => {
+                    self.x3x4();
+                }
+                Stage::X5 => {
+                    self.x5();
+                }
+                Stage::X6 => {
+                    self.x6();
+                }
+                Stage::X8 => {
+                    obs.on_backtrack();
+                    match self.x8() {
+                        true => {}
+                        false => return None,
+                    }
+                }
+            };
+            if self.last_error.is_some() {
+                return None;
+            }
+        }
+    }
+
+    /// Counts every solution without building a single `Vec<String>` along
+    /// the way
+    ///
+    /// Drives the same X2..X8 loop as [solve](Solver::solve), but the X2
+    /// win-check only inspects [elements](Solver::elements)'s link structure
+    /// and increments a counter -- unlike [x2](Solver::x2) it never calls
+    /// [output](Solver::output) (or anything that reads `self.names` or
+    /// `self.spacer_ids`), so counting a large search space this way skips
+    /// the `Vec<String>` allocation and name clone that
+    /// `Iterator::count()` (via [solve](Solver::solve)) pays for on every
+    /// single solution.
+    ///
+    /// [with_symmetry_pruner](Solver::with_symmetry_pruner) and
+    /// [enable_dup_detection](Solver::enable_dup_detection) both dedupe by
+    /// inspecting a finished solution's names or indices, which this
+    /// shortcut never builds -- so they have no effect here. A solver using
+    /// either should count with `s.count()` (via the `Iterator` impl)
+    /// instead.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(7);
+    /// s.add_option("o1", &[3, 5])
+    ///     .add_option("o2", &[1, 4, 7])
+    ///     .add_option("o3", &[2, 3, 6])
+    ///     .add_option("o4", &[1, 4, 6])
+    ///     .add_option("o5", &[2, 7])
+    ///     .add_option("o6", &[4, 5, 7]);
+    ///
+    /// assert_eq!(s.count_solutions(), 1);
+    /// ```
+    pub fn count_solutions(&mut self) -> usize {
+        self.started = true;
+        if self.last_error.is_some() {
+            return 0;
+        }
+
+        let mut count = 0;
+        loop {
+            match self.stage {
+                Stage::X2 => {
+                    if self.elements[0].r() == 0 || self.elements[0].r() >= self.optional {
+                        count += 1;
+                        self.stage = Stage::X8;
+                    } else {
+                        self.stage = Stage::X3;
+                    }
+                }
+                Stage::X3 => {
+                    self.x3x4();
+                }
+                Stage::X5 => {
+                    self.x5();
+                }
+                Stage::X6 => {
+                    self.x6();
+                }
+                Stage::X8 => {
+                    if !self.x8() {
+                        return count;
+                    }
+                }
+            };
+            if self.last_error.is_some() {
+                return count;
+            }
+        }
+    }
+
+    /// Drives the search one semantically meaningful action at a time,
+    /// instead of one FSM stage ([step](Solver::step)) or one full solution
+    /// ([solve](Solver::solve))
+    ///
+    /// Meant for animating the dancing-links structure: a `Cover`/`Uncover`
+    /// per item as it's hidden or restored, a `Descend`/`Ascend` per level
+    /// change, and a `Solution` whenever one is found. One `step()` call can
+    /// itself cover several items in a row (see [x5](Solver::x5)'s sweep
+    /// across an option's row), which shows up here as several `Cover`
+    /// events followed by one `Descend` rather than being collapsed into a
+    /// single opaque transition.
+    /// ```
+    ///# use dlx_rs::solver::{Solver, SearchEvent};
+    ///
+    /// let mut s: Solver = Solver::new(1);
+    /// s.add_option("o1", &[1]);
+    ///
+    /// let events: Vec<SearchEvent> = s.events().collect();
+    /// assert_eq!(
+    ///     events,
+    ///     vec![
+    ///         SearchEvent::Cover(1),
+    ///         SearchEvent::Descend,
+    ///         SearchEvent::Solution(vec!["o1".to_string()]),
+    ///         SearchEvent::Ascend,
+    ///         SearchEvent::Uncover(1),
+    ///     ]
+    /// );
+    /// ```
+    pub fn events(&mut self) -> SearchEvents<'_, M> {
+        self.event_queue.get_or_insert_with(VecDeque::new);
+        SearchEvents { solver: self }
+    }
+
+    /// Returns the option chosen at each committed level `0..self.l`,
+    /// without requiring the search to have finished or yielded a full
+    /// solution
+    ///
+    /// This is [output](Solver::output) by another name, for callers
+    /// driving the search one [step](Solver::step) at a time that want to
+    /// show the in-progress partial solution, not just a completed one
+    pub fn current_partial(&self) -> Vec<String> {
+        self.output()
+    }
+
+    /// Returns a solution in a human-understandable form
+    ///
+    /// The solution vector `sol_vec` stores each of the OptionElements which
+    /// were used to cover the items in the solution.  To turn this into
+    /// something understandable we find the spacer to its right, and use this
+    /// with a lookup table created earlier to map this to the names of options
+    ///
+    // TODO: Is it useful to have the double map? We don't used spacer_ids for
+    //       anything else, so could condense it into a single HashMap
+    pub fn output(&self) -> Vec<String> {
+        self.output_shared().iter().map(|x| x.to_string()).collect()
+    }
+
+    /// Like [output](Solver::output), but shares each option name's
+    /// storage instead of cloning a fresh `String`
+    ///
+    /// Since `self.names` already stores `Arc<str>`, this just bumps a
+    /// refcount per name rather than allocating and copying, which matters
+    /// when the same small set of option names recurs across many
+    /// solutions -- see [into_iter_owned_names](Solver::into_iter_owned_names).
+    fn output_shared(&self) -> Vec<Arc<str>> {
+        self.sol_vec
+            .iter()
+            .take(self.l)
+            .map(|&x| self.spacer_for(x))
+            .map(|x| self.spacer_ids[&x])
+            .map(|x| self.names[x].clone())
+            .collect()
+    }
+
+    /// Returns the metadata attached (via
+    /// [add_option_with_meta](Solver::add_option_with_meta)) to each option
+    /// in the current solution, in the same order as [output](Solver::output)
+    ///
+    /// Options added with plain [add_option](Solver::add_option) have no
+    /// metadata, and so appear as `None`
+    pub fn output_meta(&self) -> Vec<Option<&M>> {
+        self.sol_vec
+            .iter()
+            .take(self.l)
+            .map(|&x| self.spacer_for(x))
+            .map(|x| self.spacer_ids[&x])
+            .map(|x| self.meta[x].as_ref())
+            .collect()
+    }
+
+    /// Returns the option index (its position in [add_option](Solver::add_option)
+    /// insertion order) for each option in the current solution, in the
+    /// same order as [output](Solver::output)
+    pub fn output_indices(&self) -> Vec<usize> {
+        self.sol_vec
+            .iter()
+            .take(self.l)
+            .map(|&x| self.spacer_for(x))
+            .map(|x| self.spacer_ids[&x])
+            .collect()
+    }
+
+    /// Returns the current solution as a map from each mandatory item it
+    /// covers to the name of the option that covers it
+    ///
+    /// This is a decode convenience over [output](Solver::output) and
+    /// `self.options`: the same information, indexed by item rather than
+    /// listed by option, e.g. for [Sudoku](crate::sudoku::Sudoku) this
+    /// answers "what digit is in cell item X" directly. Optional items
+    /// (see [new_optional](Solver::new_optional)) left uncovered by the
+    /// solution are simply absent from the map.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2]);
+    ///
+    /// let sol = s.next().unwrap();
+    /// assert_eq!(sol, vec!["o1".to_string(), "o2".to_string()]);
+    /// assert_eq!(s.output_map()[&1], "o1");
+    /// assert_eq!(s.output_map()[&2], "o2");
+    /// ```
+    pub fn output_map(&self) -> HashMap<Index, String> {
+        let mut map = HashMap::new();
+        for &x in self.sol_vec.iter().take(self.l) {
+            let spacer = self.spacer_for(x);
+            let name = &self.names[self.spacer_ids[&spacer]];
+            for &(item, _) in &self.options[&spacer] {
+                map.insert(item, name.to_string());
+            }
+        }
+        map
+    }
+
+    /// Returns every item index covered by the current solution's options,
+    /// i.e. the union of `self.options[chosen]` over each chosen option
+    ///
+    /// This is a decode helper over [output](Solver::output) and
+    /// `self.options`, useful alongside
+    /// [validate_solution](Solver::validate_solution) to confirm which
+    /// items -- mandatory and optional -- a solution actually covers. For a
+    /// valid solution this contains every mandatory item exactly once, plus
+    /// whichever optional items happened to be covered.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2, 3]);
+    ///
+    /// s.next();
+    /// let mut coverage = s.solution_coverage();
+    /// coverage.sort();
+    /// assert_eq!(coverage, vec![1, 2, 3]);
+    /// ```
+    pub fn solution_coverage(&self) -> Vec<Index> {
+        self.sol_vec
+            .iter()
+            .take(self.l)
+            .map(|&x| self.spacer_for(x))
+            .flat_map(|spacer| self.options[&spacer].iter().map(|&(item, _)| item))
+            .collect()
+    }
+
+    /// Returns the subset of the current solution's option names that
+    /// start with `prefix`
+    ///
+    /// Useful when one exact-cover model answers several questions at
+    /// once, e.g. options named `Row:...` and `Colour:...` that partition
+    /// a single solution into two independent "views"; this is a
+    /// decode-layer convenience over [output](Solver::output), which
+    /// already holds every name needed
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("Row:1", &[1]).add_option("Colour:red", &[2]);
+    ///
+    /// s.next();
+    /// assert_eq!(s.output_filtered("Row:"), vec!["Row:1".to_string()]);
+    /// assert_eq!(s.output_filtered("Colour:"), vec!["Colour:red".to_string()]);
+    /// ```
+    pub fn output_filtered(&self, prefix: &str) -> Vec<String> {
+        self.output()
+            .into_iter()
+            .filter(|name| name.starts_with(prefix))
+            .collect()
+    }
+
+    /// Like [output](Solver::output), but passes each option name through
+    /// `f` first
+    ///
+    /// Useful when option names are packed with more information than a
+    /// report wants to show verbatim, e.g. turning
+    /// [Sudoku](crate::sudoku::Sudoku)'s `R5C3#7` into `(5,3)=7`, without
+    /// having to re-parse every name downstream of every solution.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(1);
+    /// s.add_option("R5C3#7", &[1]);
+    ///
+    /// s.next();
+    /// assert_eq!(
+    ///     s.output_with(|name| name.replace('#', "=")),
+    ///     vec!["R5C3=7".to_string()]
+    /// );
+    /// ```
+    pub fn output_with(&self, f: impl Fn(&str) -> String) -> Vec<String> {
+        self.output().iter().map(|name| f(name)).collect()
+    }
+
+    /// Stage X2 of Algorithm X
+    /// If rlink(0) = 0, then all items are covered, so return current solution
+    /// and also go to X8
+    ///
+    /// `self.optional` holds the index of the first optional item: items
+    /// are linked into the circular list in ascending index order and
+    /// relinking preserves that order, so every mandatory item (index
+    /// `1..self.optional`) always appears before every optional item
+    /// (index `self.optional..=self.items`) in the list headed by
+    /// `elements[0]`. That means `rlink(0) >= self.optional` (including
+    /// `rlink(0) == 0`, the empty-list case) is exactly "every remaining
+    /// uncovered item is optional", i.e. the search is at a valid, complete
+    /// solution -- optional items never need to be covered, only mandatory
+    /// ones
+    fn x2(&mut self) -> Option<Vec<String>> {
+        //println!("State:");
+        //println!("{}",self);
+        //println!("RLINK: {}",self.elements[0].r());
+        if self.elements[0].r() == 0 || self.elements[0].r() >= self.optional {
+            if self.yielding {
+                self.yielding = false;
+
+                if let Some(canon) = self.symmetry_pruner.clone() {
+                    let signature = canon(&self.output_indices());
+                    if !self.seen_signatures.insert(signature) {
+                        // Already seen a solution with this canonical
+                        // signature: drop this one without ever building
+                        // its Vec<String>, and resume the search from here
+                        self.yielding = true;
+                        self.stage = Stage::X8;
+                        return None;
+                    }
+                }
+
+                let sol = self.output();
+                if self.dup_detection && !self.seen_solutions.insert(sol.clone()) {
+                    self.saw_duplicate = true;
+                }
+                self.push_event(SearchEvent::Solution(sol.clone()));
+                return Some(sol);
+            } else {
+                self.yielding = true;
+                self.stage = Stage::X8;
+                return None;
+            }
+        }
+        self.stage = Stage::X3;
+        None
+    }
+
+    /// Stages X3 and X4 of algorithm X
+    ///
+    /// X3: Choose item `min_idx` according to [Heuristic] (MRV by default,
+    /// see [set_heuristic](Solver::set_heuristic)), unless
+    /// [set_item_order](Solver::set_item_order) has fixed a branching order,
+    /// in which case the next not-yet-covered mandatory item in that order
+    /// is used instead
+    ///
+    /// Every branch below is bounded by `item < self.optional` (or the
+    /// equivalent `idx < self.optional` loop guard), so only a mandatory
+    /// item (index `1..self.optional`) is ever selected -- an optional item
+    /// is never covered just to satisfy branching, even if it has `l == 0`
+    /// (no options at all cover it). Branching on such a dead item would be
+    /// a bug: [cover](Solver::cover) would immediately find no options to
+    /// try and backtrack, discarding otherwise-valid solutions that simply
+    /// leave that optional item uncovered.
+    ///
+    /// X4: Cover the chosen item
+    fn x3x4(&mut self) -> Option<Vec<String>> {
+        // X3
+        let ordered_idx = self.item_order.as_ref().and_then(|order| {
+            order
+                .iter()
+                .copied()
+                .find(|&item| item < self.optional && self.is_item_active(item))
+        });
+
+        self.idx = match ordered_idx {
+            Some(item) => item,
+            None if self.heuristic == Heuristic::FirstFit => {
+                // Branch on whichever uncovered mandatory item comes first,
+                // ignoring how many options cover it -- see [Heuristic]
+                self.elements[0].r()
+            }
+            None => {
+                // Heuristic we choose is MRV
+
+                // Walk along items and find minimum l. Mandatory items
+                // always come first in the item list (they were linked in
+                // ascending index order and relinking preserves that), so
+                // this can stop as soon as it reaches the first optional
+                // one
+                let mut idx = self.elements[0].r();
+                let mut min_idx = self.elements[0].r();
+                let mut min_l = self.elements[idx].get_l();
+                while idx != 0 && idx < self.optional {
+                    let l = self.elements[idx].get_l();
+                    // With include_optional_in_mrv on, a tie in l is broken
+                    // in favour of whichever item shares a row with the
+                    // most tightly-constrained optional item -- see
+                    // [include_optional_in_mrv](Solver::include_optional_in_mrv)
+                    let better = l < min_l
+                        || (self.include_optional_in_mrv
+                            && l == min_l
+                            && self.optional_tiebreak_score(idx)
+                                < self.optional_tiebreak_score(min_idx));
+                    if better {
+                        min_l = l;
+                        min_idx = idx;
+                    }
+                    idx = self.elements[idx].r();
+                }
+
+                min_idx
+            }
+        };
+
+        // X4
+        // Cover i
+
+        //println!("Covering item X4: {}", self.idx);
+        if let Err(e) = self.cover(self.idx) {
+            self.fail(e);
+            return None;
+        }
+        self.push_event(SearchEvent::Cover(self.idx));
+
+        // Set x_l <- DLINK(i), or ULINK(i) under Reverse traversal
+        let x_l = match self.traversal {
+            Traversal::Natural => self.elements[self.idx].d(),
+            Traversal::Reverse => self.elements[self.idx].u(),
+        };
+
+        // Save x_l in current guesses
+        //     println!("self.l: {}",self.l);
+        self.sol_vec[self.l] = x_l;
+
+        self.stage = Stage::X5;
+        None
+    }
+
+    /// Stages X5 and X7 of Algorithm X
+    ///
+    /// Try x_l
+    ///
+    /// If x_l = i, then we are out of options and execute X7: backtrack
+    ///
+    /// Otherwise, cover all other items in option x_l, increase level and go back to X2
+    ///
+    fn x5(&mut self) -> Option<Vec<String>> {
+        // X5
+        // Try x_l
+        // If x_l = i, then we are out of options and go to X7
+        // Otherwise, cover all other items in option x_l, increase level and go back to X2
+        //        println!("Partial sol: {:?}", &self.sol_vec[..self.l]);
+
+        // Try xl
+        let x_l = self.sol_vec[self.l];
+        //        println!("Trying x_{}= {}", self.l, x_l);
+        //        println!("idx: {}", self.idx);
+
+        // If out of options (x_l reads downwards from self.idx, so have looped back around), backtrack
+        if x_l == self.idx {
+            // X7
+            // Backtrack: Uncover item (i)
+
+            //            println!("Uncovering X7: {}", x_l);
+            if let Err(e) = self.uncover(x_l) {
+                self.fail(e);
+                return None;
+            }
+            self.push_event(SearchEvent::Uncover(x_l));
+            self.stage = Stage::X8;
+            return None;
+        }
+
+        #[cfg(feature = "fast_single_item")]
+        if self.is_single_item_option(x_l) {
+            // No other items share this row, so the walk below would
+            // immediately hop onto the closing spacer and wrap straight
+            // back to `x_l` without covering anything
+            self.l += 1;
+            self.push_event(SearchEvent::Descend);
+            self.stage = Stage::X2;
+            return None;
+        }
+
+        let mut p = x_l + 1;
+        while p != x_l {
+            //            println!("p: {}", p);
+
+            match &self.elements[p] {
+                Link::Spacer(_) => {
+                    // If a spacer, then hop up one link
+                    p = self.elements[p].u();
+                }
+                Link::OptionElement(_) => {
+                    //                    println!("Covering X5: {}", j);
+                    //                    println!("State:");
+                    //                    println!("{}", self);
+                    let j = self.elements[p].top();
+
+                    // commit() covers j outright unless this node carries a
+                    // color, in which case it negotiates a shared color for
+                    // j instead -- see add_option_colored
+                    if let Err(e) = self.commit(p, self.l) {
+                        self.fail(e);
+                        return None;
+                    }
+                    self.push_event(SearchEvent::Cover(j));
+                }
+                Link::Item(x) => {
+                    panic!("Trying an item {:?}", x);
+                }
+            };
+            p += 1;
+        }
+        //        println!("--");
+
+        self.l += 1;
+        self.push_event(SearchEvent::Descend);
+        self.stage = Stage::X2;
+        None
+    }
+
+    /// Stage X6 of Algorithm X
+    ///
+    /// Try again
+    ///
+    /// Uncover items != i in option x_l, then set x_l = DLINK(x_l): this is how we move through all of the options
+    fn x6(&mut self) -> Option<Vec<String>> {
+        let x_l = self.sol_vec[self.l];
+
+        #[cfg(feature = "fast_single_item")]
+        if self.is_single_item_option(x_l) {
+            // Mirror image of the X5 fast path: a single-item row was
+            // never covered by anything other than `x_l` itself, so there
+            // is nothing for the walk below to uncover
+            self.idx = self.elements[x_l].top();
+            self.sol_vec[self.l] = self.elements[x_l].d();
+            self.stage = Stage::X5;
+            return None;
+        }
+
+        let mut p = x_l - 1;
+
+        while p != x_l {
+            let j = self.elements[p].top();
+            if j == 0 {
+                p = self.elements[p].d();
+            } else {
+                //                println!("Uncovering X6: {}",j);
+                if let Err(e) = self.uncommit(p, self.l) {
+                    self.fail(e);
+                    return None;
+                }
+                self.push_event(SearchEvent::Uncover(j));
+            }
+            p -= 1;
+        }
+        self.idx = self.elements[x_l].top();
+        self.sol_vec[self.l] = match self.traversal {
+            Traversal::Natural => self.elements[x_l].d(),
+            Traversal::Reverse => self.elements[x_l].u(),
+        };
+
+        self.stage = Stage::X5;
+        None
+    }
+
+    /// Stage X8 of Algorithm X
+    /// Leave level l
+    /// Terminate if l=0, otherwise l=l-1, go to X6
+    fn x8(&mut self) -> bool {
+        // X8
+        match self.l {
+            0 => false,
+            _ => {
+                self.l -= 1;
+                self.push_event(SearchEvent::Ascend);
+                self.stage = Stage::X6;
+                true
+            }
+        }
+    }
+
+    /// For [include_optional_in_mrv](Solver::include_optional_in_mrv)
+    /// tie-breaking: the fewest options covering any optional item sharing
+    /// a row with `item`, or `usize::MAX` if none of `item`'s rows touch
+    /// an optional item. Lower means branching on `item` is more likely
+    /// to also constrain a tightly-bound optional item
+    fn optional_tiebreak_score(&self, item: Index) -> usize {
+        let mut best = usize::MAX;
+        let mut p = self.elements[item].d();
+        while p != item {
+            let spacer = self.spacer_for(p);
+            for &(other, _) in &self.options[&spacer] {
+                if other >= self.optional {
+                    best = best.min(self.elements[other].get_l());
+                }
+            }
+            p = self.elements[p].d();
+        }
+        best
+    }
+
+    /// True when `p` is the only [OptionElement](Link::OptionElement) in
+    /// its row, i.e. there are no "other" items for
+    /// [x5](Solver::x5)/[x6](Solver::x6) to cover or uncover on the way
+    /// through it. The dancing-links spacer wrap-around already makes
+    /// those walks a no-op for such rows, so this only exists to let
+    /// [fast_single_item](Solver) skip straight past the redundant hop
+    #[cfg(feature = "fast_single_item")]
+    fn is_single_item_option(&self, p: Index) -> bool {
+        matches!(self.elements[p + 1], Link::Spacer(_)) && self.elements[p + 1].u() == p
+    }
+
+    /// Takes in a non-item node and steps rightwards along `self.elements` the
+    /// until a spacer is found, upon which the index is returned
+    fn spacer_for(&self, x: Index) -> Index {
+        let mut p = x;
+        loop {
+            match self.elements[p] {
+                Link::Spacer(_) => return p,
+                Link::OptionElement(_) => p += 1,
+                Link::Item(_) => panic!("Somehow ended up on an item"),
+            };
+        }
+    }
+
+    /// Selects an option with the name `name` When setting up a general
+    /// constraint solution, this is how to search for specific answers e.g. a
+    /// Sudoku has all the constraints (items and options), and then the squares
+    /// filled out in the specific problem need to be selected
+    ///
+    /// So for the problem
+    ///
+    /// ```text
+    ///    i1  i2  i3
+    /// o1  1   0   0
+    /// o2  1   0   0
+    /// o3  0   1   1
+    /// ```
+    /// Clearly *both* \[o1,o3\] and \[o2,o3\] are solutions, but if we select o1, then only one solution remains
+    ///
+    /// Like [cover](Solver::cover), this is a construction-time operation:
+    /// it must be called before iteration begins, since it mutates the
+    /// links directly rather than going through the search stages. Calling
+    /// it after [solve](Solver::solve)/[next](Iterator::next) has already
+    /// started the search returns `Err(SolverError::AlreadyIterating)`
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    ///
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[1])
+    ///     .add_option("o3", &[2, 3]);
+    ///
+    /// // First get all solutions
+    /// let sols: Vec<Vec<String>> = s.clone().collect();
+    /// assert_eq!( sols.len(), 2);
+    /// assert_eq!( vec!["o3", "o1"], sols[0]);
+    /// assert_eq!( vec!["o3", "o2"], sols[1]);
+    ///
+    ///
+    /// // Now select o1 and get all solutions
+    /// s.select("o1").unwrap();
+    /// assert_eq!( vec!["o3"], s.next().unwrap());
+    /// ```
+    pub fn select(&mut self, name: &str) -> Result<(), SolverError> {
+        if self.started {
+            return Err(SolverError::AlreadyIterating);
+        }
+
+        // This selects an option by doing the followings
+
+        // First get the spacer position of the option by firstly finding which
+        // option it was
+        let id = match self.names.iter().position(|x| x.as_ref() == name) {
+            Some(z) => z,
+            None => return Err(SolverError::UnknownOption(name.to_string())),
+        };
+        // spacer_by_index caches this lookup (built alongside spacer_ids in
+        // add_option), so finding the option's spacer is O(1) rather than
+        // walking `id` links down from the root spacer every call -- this
+        // used to make applying many givens up front (e.g. a Sudoku's
+        // initial select() calls) quadratic in the number of options
+        let spacer_id = self.spacer_by_index[id];
+
+        // Now have the spacer node: cycle around and hide everything until we are at the next spacer mode
+        let mut p = spacer_id + 1;
+
+        loop {
+            match self.elements[p] {
+                Link::OptionElement(_) => {
+                    self.cover(self.elements[p].top()).unwrap();
+                    p += 1;
+                }
+                Link::Spacer(_) => break,
+                Link::Item(_) => break,
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Selects every option whose name satisfies `predicate`, in the same
+    /// way repeatedly calling [select](Solver::select) would
+    ///
+    /// This is useful when options are named with structured prefixes
+    /// (like Sudoku's `R5C3#7`) and the caller wants to force a whole
+    /// pattern of them at once, e.g. `|name| name.starts_with("R5C3#")`,
+    /// without enumerating exact names one by one.
+    ///
+    /// Unlike calling [select](Solver::select) in a loop, this tolerates
+    /// overlapping matches: if an earlier match already covered one of a
+    /// later match's items (because the two share an item), that item is
+    /// simply skipped instead of being covered twice. Like
+    /// [select](Solver::select), this is a construction-time operation and
+    /// returns `Err(SolverError::AlreadyIterating)` once the search has
+    /// started.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    /// s.add_option("R1#1", &[1])
+    ///     .add_option("R1#2", &[1])
+    ///     .add_option("R2#1", &[2, 3])
+    ///     .add_option("R2#2", &[2]);
+    ///
+    /// // Force every option whose name starts with "R1#"
+    /// s.select_matching(|name| name.starts_with("R1#")).unwrap();
+    /// assert_eq!(s.next(), Some(vec!["R2#1".to_string()]));
+    /// ```
+    pub fn select_matching(
+        &mut self,
+        predicate: impl Fn(&str) -> bool,
+    ) -> Result<(), SolverError> {
+        if self.started {
+            return Err(SolverError::AlreadyIterating);
+        }
+
+        let matching_ids: Vec<usize> = self
+            .names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| predicate(name))
+            .map(|(id, _)| id)
+            .collect();
+
+        for id in matching_ids {
+            let spacer_id = self.spacer_by_index[id];
+
+            let mut p = spacer_id + 1;
+            loop {
+                match self.elements[p] {
+                    Link::OptionElement(_) => {
+                        let item = self.elements[p].top();
+                        // An earlier match may already have covered this
+                        // item (the two options share it), which removes
+                        // it from the header list; covering it again
+                        // would hide already-hidden rows a second time
+                        if self.is_item_active(item) {
+                            self.cover(item).unwrap();
+                        }
+                        p += 1;
+                    }
+                    Link::Spacer(_) => break,
+                    Link::Item(_) => break,
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restricts item `item` so that only options named in
+    /// `allowed_options` may ever cover it: every other option currently
+    /// covering `item` is deleted from the problem entirely (not just from
+    /// `item`'s own column), so it can't sneak back in by being chosen via
+    /// one of its other items either
+    ///
+    /// This is finer-grained than [select](Solver::select) (which commits
+    /// to one specific option): it models "item 5 must be covered by
+    /// option o3 or o7" directly, e.g. a Sudoku cell that can only take
+    /// the values 3 or 7. Must be called before iteration begins, since
+    /// (like [select](Solver::select)) it walks the live linked lists.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[1])
+    ///     .add_option("o3", &[1, 2])
+    ///     .add_option("o4", &[2]);
+    ///
+    /// // Item 1 may now only be covered by o1 or o3
+    /// s.constrain_item(1, &["o1", "o3"]).unwrap();
+    ///
+    /// let sols: Vec<Vec<String>> = s.collect();
+    /// assert_eq!(sols, vec![vec!["o1".to_string(), "o4".to_string()], vec!["o3".to_string()]]);
+    /// ```
+    pub fn constrain_item(
+        &mut self,
+        item: Index,
+        allowed_options: &[&str],
+    ) -> Result<(), SolverError> {
+        if self.started {
+            return Err(SolverError::AlreadyIterating);
+        }
+        if item == 0 || item >= self.elements.len() || !matches!(self.elements[item], Link::Item(_))
+        {
+            return Err(SolverError::ItemOutOfRange(item));
+        }
+        for &name in allowed_options {
+            if !self.names.iter().any(|n| n.as_ref() == name) {
+                return Err(SolverError::UnknownOption(name.to_string()));
+            }
+        }
+
+        let mut p = self.elements[item].d();
+        while p != item {
+            let next = self.elements[p].d();
+            let spacer = self.spacer_for(p);
+            let option_name = &self.names[self.spacer_ids[&spacer]];
+            if !allowed_options.contains(&option_name.as_ref()) {
+                self.delete_option_row(spacer);
+            }
+            p = next;
+        }
+
+        Ok(())
+    }
+
+    /// Removes every element of the option whose row ends at `spacer` from
+    /// its item's vertical chain, deleting the option from the problem
+    /// entirely
+    fn delete_option_row(&mut self, spacer: Index) {
+        let len = self.options[&spacer].len();
+        for q in (spacer - len)..spacer {
+            let item = self.elements[q].top();
+            let u = self.elements[q].u();
+            let d = self.elements[q].d();
+            self.elements[u].set_d(d);
+            self.elements[d].set_u(u);
+            self.elements[item].dec_l();
+        }
+    }
+
+    /// Validates an externally-supplied candidate solution, given as a list
+    /// of option names
+    ///
+    /// Confirms every name exists, that every mandatory item is covered
+    /// exactly once, and that no item (mandatory or optional) is covered
+    /// more than once -- except for a [colored](Solver::add_option_colored)
+    /// item, which any number of the given options may cover as long as
+    /// they all agree on its color, mirroring the commit/uncommit
+    /// negotiation [x5](Solver::x5)/[x6](Solver::x6) perform during search.
+    /// This does not touch the search state at all, so it may be called
+    /// freely, e.g. to check a solution produced elsewhere or submitted by
+    /// a user.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::{Solver, SolverError};
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[1])
+    ///     .add_option("o3", &[2, 3]);
+    ///
+    /// assert_eq!(s.validate_solution(&["o1", "o3"]), Ok(()));
+    /// assert_eq!(
+    ///     s.validate_solution(&["o1", "o2", "o3"]),
+    ///     Err(SolverError::ItemOverCovered(1))
+    /// );
+    /// assert_eq!(s.validate_solution(&["o3"]), Err(SolverError::ItemUncovered(1)));
+    ///
+    /// // Two options agreeing on a color for an optional item coexist fine
+    /// let mut c: Solver = Solver::new_optional(2, 1);
+    /// c.add_option_colored("o1", &[1], &[(3, 7)])
+    ///     .add_option_colored("o2", &[2], &[(3, 7)]);
+    /// assert_eq!(c.validate_solution(&["o1", "o2"]), Ok(()));
+    /// ```
+    pub fn validate_solution(&self, names: &[&str]) -> Result<(), SolverError> {
+        let mut claims: Vec<Vec<Option<u32>>> = vec![Vec::new(); self.items + 1];
+
+        for &name in names {
+            let (&spacer, _) = self
+                .spacer_ids
+                .iter()
+                .find(|(_, &id)| &*self.names[id] == name)
+                .ok_or_else(|| SolverError::UnknownOption(name.to_string()))?;
+            for &(item, color) in &self.options[&spacer] {
+                claims[item].push(color);
+            }
+        }
+
+        for (item, item_claims) in claims.iter().enumerate().skip(1) {
+            let uncolored = item_claims.iter().filter(|c| c.is_none()).count();
+            let colors: HashSet<u32> = item_claims.iter().filter_map(|&c| c).collect();
+
+            // An uncolored claim covers its item exclusively, so more than
+            // one (or mixing one with a colored claim) always conflicts;
+            // colored claims instead only conflict when they disagree on
+            // the color, exactly like commit()
+            let conflicting = uncolored > 1 || (uncolored > 0 && !colors.is_empty()) || colors.len() > 1;
+            let covered = !conflicting && (uncolored == 1 || !colors.is_empty());
+
+            if item < self.optional {
+                if conflicting {
+                    return Err(SolverError::ItemOverCovered(item));
+                }
+                if !covered {
+                    return Err(SolverError::ItemUncovered(item));
+                }
+            } else if conflicting {
+                return Err(SolverError::ItemOverCovered(item));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Positions the search as if it had just found `names` as a solution,
+    /// so that the *next* call to [next](Iterator::next) backtracks past it
+    /// and searches for the following one, rather than starting over
+    ///
+    /// `names` must be a valid solution, exactly as checked by
+    /// [validate_solution](Solver::validate_solution) (which this calls
+    /// first). Each option is committed in the order given by covering the
+    /// item [x3x4](Solver::x3x4)'s default MRV rule branches on at that
+    /// level (the option's first item, if the option doesn't cover that
+    /// one), then committing the rest of its row the way [x5](Solver::x5)
+    /// would (negotiating a shared [color](Solver::add_option_colored)
+    /// rather than covering outright, where the row calls for it), growing
+    /// `sol_vec` one level at a time -- so, under the default heuristic, the
+    /// resulting state is indistinguishable from one [next](Iterator::next)
+    /// had actually reached by search. A branching rule chosen with
+    /// [set_heuristic](Solver::set_heuristic) or
+    /// [set_item_order](Solver::set_item_order) is not consulted here.
+    ///
+    /// Enables resumable search: pair this with a serialized `names` list
+    /// from an earlier run's [output](Solver::output) to continue
+    /// enumerating from where a previous process left off, without
+    /// re-visiting every solution before it. Like [select](Solver::select),
+    /// this is a construction-time operation and returns
+    /// `Err(SolverError::AlreadyIterating)` once the search has started.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("a1", &[1])
+    ///     .add_option("a2", &[1])
+    ///     .add_option("b", &[2]);
+    ///
+    /// let all: Vec<Vec<String>> = s.clone().collect();
+    /// let first = &all[0];
+    ///
+    /// let mut resumed: Solver = Solver::new(2);
+    /// resumed
+    ///     .add_option("a1", &[1])
+    ///     .add_option("a2", &[1])
+    ///     .add_option("b", &[2]);
+    /// resumed
+    ///     .seed_from_solution(&first.iter().map(String::as_str).collect::<Vec<_>>())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(resumed.collect::<Vec<_>>(), all[1..]);
+    /// ```
+    pub fn seed_from_solution(&mut self, names: &[&str]) -> Result<(), SolverError> {
+        if self.started {
+            return Err(SolverError::AlreadyIterating);
+        }
+        self.validate_solution(names)?;
+
+        for &name in names {
+            let (&spacer, _) = self
+                .spacer_ids
+                .iter()
+                .find(|(_, &id)| &*self.names[id] == name)
+                .ok_or_else(|| SolverError::UnknownOption(name.to_string()))?;
+
+            // Row layout mirrors delete_option_row: the row's elements sit
+            // immediately before its closing spacer, in add_option order.
+            // Start from the row's element in the column MRV picks, as x3x4
+            // would
+            let row_start = spacer - self.options[&spacer].len();
+            let p_first = self
+                .root_mrv_item()
+                .and_then(|mrv| (row_start..spacer).find(|&q| self.elements[q].top() == mrv))
+                .unwrap_or(row_start);
+            let idx = self.elements[p_first].top();
+
+            // X4: cover the item this level branches on
+            self.cover(idx).unwrap();
+            self.sol_vec[self.l] = p_first;
+
+            // X5: commit every other item in the chosen row, the way x5
+            // does -- covering it outright, or negotiating a shared color
+            // if the row claims one (see commit)
+            let mut p = p_first + 1;
+            while p != p_first {
+                match self.elements[p] {
+                    Link::Spacer(_) => p = self.elements[p].u(),
+                    Link::OptionElement(_) => {
+                        self.commit(p, self.l).unwrap();
+                    }
+                    Link::Item(_) => unreachable!("row walk landed on an item"),
+                }
+                p += 1;
+            }
+
+            self.l += 1;
+        }
+
+        self.stage = Stage::X2;
+        self.yielding = false;
+        Ok(())
+    }
+
+    /// Snapshots the current search depth and committed options as a
+    /// [SearchCursor], which can be stored, serialized, and later applied
+    /// to a freshly-built, structurally identical solver with
+    /// [resume](Solver::resume) to continue the search from here
+    ///
+    /// Only meaningful between [step](Solver::step) calls (or before the
+    /// search has started, for `l == 0`): [solve](Solver::solve)/
+    /// [next](Iterator::next) loop through stages without handing control
+    /// back at a level boundary, so there's no useful moment to call this
+    /// from inside them.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///# use dlx_rs::solver::StepOutcome;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("a1", &[1])
+    ///     .add_option("a2", &[1])
+    ///     .add_option("b", &[2]);
+    ///
+    /// let all: Vec<Vec<String>> = s.clone().collect();
+    ///
+    /// // Step until the first solution is found, then checkpoint
+    /// let mut outcome = StepOutcome::Continue;
+    /// while matches!(outcome, StepOutcome::Continue) {
+    ///     outcome = s.step();
+    /// }
+    /// assert_eq!(outcome, StepOutcome::Solution(all[0].clone()));
+    /// let cursor = s.checkpoint();
+    ///
+    /// let mut resumed: Solver = Solver::new(2);
+    /// resumed
+    ///     .add_option("a1", &[1])
+    ///     .add_option("a2", &[1])
+    ///     .add_option("b", &[2]);
+    /// resumed.resume(cursor).unwrap();
+    ///
+    /// assert_eq!(resumed.collect::<Vec<_>>(), all[1..]);
+    /// ```
+    pub fn checkpoint(&self) -> SearchCursor {
+        SearchCursor {
+            l: self.l,
+            committed: self.current_partial(),
+        }
+    }
+
+    /// Applies a [SearchCursor] taken from a structurally identical solver,
+    /// re-covering each committed option in the order it was recorded so
+    /// the search can continue from that level boundary as if it had never
+    /// paused. A cursor taken after the search had backtracked into a
+    /// level does not record which of that level's options were already
+    /// tried (see [SearchCursor]), so the search starts that level again
+    /// from its first option.
+    ///
+    /// Shares [seed_from_solution](Solver::seed_from_solution)'s
+    /// row-covering logic (including its color-aware commit of the rest of
+    /// each row), but without requiring `cursor.committed` to be a
+    /// *complete* solution -- that's exactly what lets this resume a
+    /// paused, not-yet-finished enumeration instead of only the following
+    /// one. Like `seed_from_solution`, this is a construction-time
+    /// operation and returns `Err(SolverError::AlreadyIterating)` once the
+    /// search has started.
+    pub fn resume(&mut self, cursor: SearchCursor) -> Result<(), SolverError> {
+        if self.started {
+            return Err(SolverError::AlreadyIterating);
+        }
+
+        for name in &cursor.committed {
+            let (&spacer, _) = self
+                .spacer_ids
+                .iter()
+                .find(|(_, &id)| &*self.names[id] == name)
+                .ok_or_else(|| SolverError::UnknownOption(name.clone()))?;
+
+            // Row layout mirrors delete_option_row: the row's elements sit
+            // immediately before its closing spacer, in add_option order.
+            // Start from the row's element in the column MRV picks, as x3x4
+            // would
+            let row_start = spacer - self.options[&spacer].len();
+            let p_first = self
+                .root_mrv_item()
+                .and_then(|mrv| (row_start..spacer).find(|&q| self.elements[q].top() == mrv))
+                .unwrap_or(row_start);
+            let idx = self.elements[p_first].top();
+
+            // X4: cover the item this level branches on
+            self.cover(idx)
+                .map_err(|e| SolverError::Internal(e.to_string()))?;
+            self.sol_vec[self.l] = p_first;
+
+            // X5: commit every other item in the chosen row, the way x5
+            // does -- covering it outright, or negotiating a shared color
+            // if the row claims one (see commit)
+            let mut p = p_first + 1;
+            while p != p_first {
+                match self.elements[p] {
+                    Link::Spacer(_) => p = self.elements[p].u(),
+                    Link::OptionElement(_) => {
+                        self.commit(p, self.l)
+                            .map_err(|e| SolverError::Internal(e.to_string()))?;
+                    }
+                    Link::Item(_) => unreachable!("row walk landed on an item"),
+                }
+                p += 1;
+            }
+
+            self.l += 1;
+        }
+
+        self.stage = Stage::X2;
+        // Only a cursor taken at a solution has already reported it, so only
+        // then does x2 skip the state it resumes in
+        self.yielding = !(self.elements[0].r() == 0 || self.elements[0].r() >= self.optional);
+        Ok(())
+    }
+
+    /// Consumes the solver and returns a
+    /// [ProblemDescription](ProblemDescription) of its items and options,
+    /// independent of the internal dancing-links representation
+    ///
+    /// See [from_description](Solver::from_description) for the reverse
+    /// direction; round-tripping through the two must be idempotent.
+    pub fn into_problem_description(self) -> ProblemDescription {
+        let mut by_name_id: Vec<(usize, Index)> = self
+            .spacer_ids
+            .iter()
+            .map(|(&spacer, &name_id)| (name_id, spacer))
+            .collect();
+        by_name_id.sort_unstable_by_key(|&(name_id, _)| name_id);
+
+        let num_items = self.items;
+        let num_optional = self.items - (self.optional - 1);
+        let names = self.names;
+        let mut options_by_spacer = self.options;
+
+        let options = by_name_id
+            .into_iter()
+            .map(|(name_id, spacer)| {
+                (
+                    names[name_id].to_string(),
+                    options_by_spacer.remove(&spacer).unwrap(),
+                )
+            })
+            .collect();
+
+        ProblemDescription {
+            num_items,
+            num_optional,
+            options,
+        }
+    }
+
+    /// Returns up to `n` solutions, advancing the search by exactly that
+    /// many steps (or until it's exhausted, whichever comes first)
+    ///
+    /// This is `self.by_ref().take(n).collect()` under a clearer name,
+    /// avoiding the awkwardness of `.take(n)` otherwise needing an owned
+    /// (rather than borrowed) iterator.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[1])
+    ///     .add_option("o3", &[2, 3]);
+    ///
+    /// // Only 2 solutions exist, so asking for 5 returns fewer than 5
+    /// assert_eq!(s.first_n_solutions(5).len(), 2);
+    /// ```
+    pub fn first_n_solutions(&mut self, n: usize) -> Vec<Vec<String>> {
+        self.by_ref().take(n).collect()
+    }
+
+    /// Exhausts the search and returns the solution count modulo `modulus`,
+    /// instead of the exact count
+    ///
+    /// Some problems -- large Aztec diamonds are a classic example -- have
+    /// astronomically many solutions, enough to overflow even a `u128`
+    /// counter. Counting modulo a modulus (e.g. a prime, for cross-checking
+    /// against a sequence like OEIS) sidesteps the overflow entirely, at
+    /// the cost of losing the exact count.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    /// s.add_option("1a", &[1]).add_option("1b", &[1])
+    ///     .add_option("2a", &[2]).add_option("2b", &[2])
+    ///     .add_option("3a", &[3]).add_option("3b", &[3]);
+    ///
+    /// // Each of the 3 independent items has 2 interchangeable options, so
+    /// // there are 2^3 = 8 solutions; 8 mod 5 = 3
+    /// assert_eq!(s.count_solutions_mod(5), 3);
+    /// ```
+    pub fn count_solutions_mod(&mut self, modulus: u64) -> u64 {
+        let mut count: u64 = 0;
+        for _ in self.by_ref() {
+            count = (count + 1) % modulus;
+        }
+        count
+    }
+
+    /// Returns `true` iff exactly one solution exists, short-circuiting
+    /// the search the instant a second solution turns up
+    ///
+    /// Validating uniqueness (a Sudoku puzzle's most important property)
+    /// doesn't need the solutions themselves, just a count capped at two --
+    /// this is that query given its own name rather than asking every
+    /// caller to reach for [count_up_to_parallel](Solver::count_up_to_parallel)
+    /// and compare the result to `1`.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut unique: Solver = Solver::new(2);
+    /// unique.add_option("o1", &[1]).add_option("o2", &[2]);
+    /// assert!(unique.has_unique_solution());
+    ///
+    /// let mut multiple: Solver = Solver::new(1);
+    /// multiple.add_option("o1", &[1]).add_option("o2", &[1]);
+    /// assert!(!multiple.has_unique_solution());
+    ///
+    /// let mut none: Solver = Solver::new(2);
+    /// none.add_option("o1", &[1]);
+    /// assert!(!none.has_unique_solution());
+    /// ```
+    pub fn has_unique_solution(&mut self) -> bool {
+        self.by_ref().take(2).count() == 1
+    }
+
+    /// Returns the first solution and stops there, documenting the common
+    /// "I only need one answer" case (e.g. solving a single Sudoku) as its
+    /// own named call instead of a `next()` the reader has to infer is
+    /// terminal
+    ///
+    /// This is `self.next()` under a clearer name: the search is a finite
+    /// state machine whose backtracking state (`self.l`, `self.sol_vec`,
+    /// `self.stage`) is intrinsic to finding even a single solution, so
+    /// there is no setup left to skip. Calling it still leaves the solver
+    /// positioned to resume from where it left off, exactly like `next()`
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1, 2]).add_option("o2", &[1]).add_option("o3", &[2]);
+    ///
+    /// assert_eq!(s.find_one(), Some(vec!["o1".to_string()]));
+    /// ```
+    pub fn find_one(&mut self) -> Option<Vec<String>> {
+        self.next()
+    }
+
+    /// Eagerly enumerates every remaining solution and returns them in
+    /// reverse discovery order
+    ///
+    /// The full solution set isn't known until the search is exhausted, so
+    /// there's no way to stream solutions "last first" lazily -- this is
+    /// `self.by_ref().collect::<Vec<_>>()` reversed, under an explicit
+    /// eager contract rather than pretending it's a lazy iterator. Handy
+    /// for tests and for presenting solutions in a preferred order.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(1);
+    /// s.add_option("o1", &[1]).add_option("o2", &[1]);
+    ///
+    /// assert_eq!(
+    ///     s.collect_reversed(),
+    ///     vec![vec!["o2".to_string()], vec!["o1".to_string()]]
+    /// );
+    /// ```
+    pub fn collect_reversed(&mut self) -> Vec<Vec<String>> {
+        let mut solutions: Vec<Vec<String>> = self.by_ref().collect();
+        solutions.reverse();
+        solutions
+    }
+
+    /// Iterates solutions as `(names, indices)` pairs, combining
+    /// [output](Solver::output) with [output_indices](Solver::output_indices)
+    /// for each one without re-searching
+    ///
+    /// Useful when a caller needs names for display and indices for lookup
+    /// into an external metadata table, without having to choose between
+    /// the two views of `self.sol_vec`.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2]);
+    ///
+    /// let (names, indices) = s.solutions_with_indices().next().unwrap();
+    /// assert_eq!(names, vec!["o1".to_string(), "o2".to_string()]);
+    /// // Indices match add_option insertion order: o1 is 0, o2 is 1
+    /// assert_eq!(indices, vec![0, 1]);
+    /// ```
+    pub fn solutions_with_indices(&mut self) -> impl Iterator<Item = (Vec<String>, Vec<usize>)> + '_ {
+        std::iter::from_fn(move || {
+            self.next()?;
+            Some((self.output(), self.output_indices()))
+        })
+    }
+
+    /// Returns the solution whose sorted set of option indices is
+    /// lexicographically smallest among all remaining solutions, or `None`
+    /// if none exist
+    ///
+    /// Branch order (which item is picked next, and in what order its
+    /// options are tried) doesn't determine the lexicographic order of the
+    /// *sorted* index set -- two solutions reached via different branching
+    /// orders can still compare either way once their indices are sorted --
+    /// so this enumerates every remaining solution and keeps the minimum,
+    /// the same exhaustive-comparison approach [feasible_options](Solver::feasible_options)
+    /// and [backbone_options](Solver::backbone_options) take for their own
+    /// "compare across every solution" questions. The result is a
+    /// deterministic, traversal- and heuristic-independent "canonical"
+    /// solution, useful for reproducible output and for tests that
+    /// shouldn't depend on search order.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[2])
+    ///     .add_option("o3", &[1, 2])
+    ///     .add_option("o4", &[2]);
+    ///
+    /// // Solutions by sorted index set: {0,1}, {0,3}, {2} -- {0,1} is smallest
+    /// assert_eq!(s.lexicographic_first_solution(), Some(vec![0, 1]));
+    /// ```
+    pub fn lexicographic_first_solution(&mut self) -> Option<Vec<usize>> {
+        self.solutions_with_indices()
+            .map(|(_, mut indices)| {
+                indices.sort_unstable();
+                indices
+            })
+            .min()
+    }
+
+    /// Iterates solutions as `(added_option_indices, removed_option_indices)`
+    /// relative to the previous solution, rather than each solution in full
+    ///
+    /// For enumerations where consecutive solutions differ in only a few
+    /// options (common in structured problems with a lot of backtracking
+    /// near the end of the search), this is cheaper to stream to a consumer
+    /// -- e.g. a UI applying incremental updates -- than re-sending the
+    /// whole [output_indices](Solver::output_indices) every time. Computed
+    /// by diffing the current solution's indices against the previous
+    /// one's; the first solution is reported as entirely "added" against an
+    /// empty starting set. Both vectors are sorted for a deterministic
+    /// order.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[2])
+    ///     .add_option("o3", &[1, 2])
+    ///     .add_option("o4", &[2]);
+    ///
+    /// // Three solutions in total: {o1,o2}, {o1,o4}, {o3}
+    /// let deltas: Vec<(Vec<usize>, Vec<usize>)> = s.solution_deltas().collect();
+    /// assert_eq!(deltas[0], (vec![0, 1], vec![])); // {o1,o2} from nothing
+    /// assert_eq!(deltas[1], (vec![3], vec![1])); // {o1,o4}: o2 out, o4 in
+    /// assert_eq!(deltas[2], (vec![2], vec![0, 3])); // {o3}: o1 and o4 out, o3 in
+    /// ```
+    pub fn solution_deltas(&mut self) -> impl Iterator<Item = (Vec<usize>, Vec<usize>)> + '_ {
+        let mut previous: HashSet<usize> = HashSet::new();
+        std::iter::from_fn(move || {
+            self.next()?;
+            let current: HashSet<usize> = self.output_indices().into_iter().collect();
+            let mut added: Vec<usize> = current.difference(&previous).copied().collect();
+            let mut removed: Vec<usize> = previous.difference(&current).copied().collect();
+            added.sort_unstable();
+            removed.sort_unstable();
+            previous = current;
+            Some((added, removed))
+        })
+    }
+
+    /// Iterates solutions in chunks of up to `batch_size`, for consumers
+    /// that process solutions in bulk (e.g. batched database inserts)
+    /// rather than one at a time
+    ///
+    /// A thin adaptor over the solution iterator: each batch is filled by
+    /// repeatedly calling [next](Solver::next) until it has `batch_size`
+    /// solutions or the search is exhausted, so the final batch may be
+    /// smaller. `batch_size` of `0` yields no batches at all.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2]);
+    /// s.add_option("o3", &[1, 2]).add_option("o4", &[2]);
+    ///
+    /// // Three solutions in total: {o1,o2}, {o1,o4}, {o3}
+    /// let batches: Vec<Vec<Vec<String>>> = s.solutions_batched(2).collect();
+    /// assert_eq!(batches.len(), 2);
+    /// assert_eq!(batches[0].len(), 2);
+    /// assert_eq!(batches[1].len(), 1);
+    /// ```
+    pub fn solutions_batched(&mut self, batch_size: usize) -> impl Iterator<Item = Vec<Vec<String>>> + '_ {
+        std::iter::from_fn(move || {
+            if batch_size == 0 {
+                return None;
+            }
+            let batch: Vec<Vec<String>> = self.by_ref().take(batch_size).collect();
+            if batch.is_empty() {
+                None
+            } else {
+                Some(batch)
+            }
+        })
+    }
+
+    /// Folds over the remaining solutions, aborting early if `f` returns
+    /// `Err`
+    ///
+    /// Generalizes [count](Iterator::count), [for_each](Iterator::for_each)
+    /// and bounded iteration into a single combinator, matching the
+    /// standard library's [Iterator::try_fold]: `f` is called once per
+    /// solution with the running accumulator and that solution's names,
+    /// and as soon as it returns `Err(e)`, folding stops and `e` is
+    /// returned without the solver advancing any further. This is driven
+    /// directly by the same FSM loop as plain iteration, so a caller that
+    /// only needs a running statistic (and an early-out condition) over
+    /// solutions never has to collect them into a `Vec` first.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[2])
+    ///     .add_option("o3", &[1, 2]);
+    ///
+    /// // Abort as soon as the running count of solutions reaches 2,
+    /// // leaving the third (o3) unvisited
+    /// let result: Result<usize, usize> = s.try_fold_solutions(0, |count, _sol| {
+    ///     let count = count + 1;
+    ///     if count >= 2 { Err(count) } else { Ok(count) }
+    /// });
+    /// assert_eq!(result, Err(2));
+    /// ```
+    pub fn try_fold_solutions<B, E>(
+        &mut self,
+        init: B,
+        mut f: impl FnMut(B, Vec<String>) -> Result<B, E>,
+    ) -> Result<B, E> {
+        let mut acc = init;
+        for sol in self.by_ref() {
+            acc = f(acc, sol)?;
+        }
+        Ok(acc)
+    }
+
+    /// Exhausts the search and returns how many solutions use each number
+    /// of options, as a map from solution size to count
+    ///
+    /// This is a lightweight analytics tool for studying a problem family:
+    /// a tightly-constrained problem (like Sudoku, always solved with
+    /// exactly 81 options) has a single-entry distribution, while a looser
+    /// set-cover-style problem typically spreads across several sizes.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///# use std::collections::BTreeMap;
+    ///
+    /// let mut s: Solver = Solver::new(3);
+    /// s.add_option("o1", &[1, 2, 3])
+    ///     .add_option("o2", &[1])
+    ///     .add_option("o3", &[2, 3]);
+    ///
+    /// // Either the single 3-item option, or the 1-item + 2-item pair
+    /// assert_eq!(
+    ///     s.solution_size_distribution(),
+    ///     BTreeMap::from([(1, 1), (2, 1)])
+    /// );
+    /// ```
+    pub fn solution_size_distribution(&mut self) -> std::collections::BTreeMap<usize, usize> {
+        let mut distribution = std::collections::BTreeMap::new();
+        for sol in self.by_ref() {
+            *distribution.entry(sol.len()).or_insert(0) += 1;
+        }
+        distribution
+    }
+
+    /// Finds the item X3's MRV heuristic would branch on first -- the
+    /// mandatory item with the fewest remaining covering options -- without
+    /// actually covering it
+    ///
+    /// Returns `None` if no mandatory item is left uncovered, meaning the
+    /// current state is already a (possibly empty) solution with nothing
+    /// left to branch on. Shared by the parallel top-level fan-out methods
+    /// ([collect_parallel_sorted](Solver::collect_parallel_sorted),
+    /// [count_up_to_parallel](Solver::count_up_to_parallel)) and
+    /// [root_branching_factor](Solver::root_branching_factor), all of which
+    /// need to know the same thing about the root of the search tree
+    /// without disturbing it.
+    fn root_mrv_item(&self) -> Option<usize> {
+        let mut idx = self.elements[0].r();
+        if idx == 0 || idx >= self.optional {
+            return None;
+        }
+        let mut min_idx = idx;
+        let mut min_l = self.elements[idx].get_l();
+        while idx != 0 && idx < self.optional {
+            let l = self.elements[idx].get_l();
+            if l < min_l {
+                min_l = l;
+                min_idx = idx;
+            }
+            idx = self.elements[idx].r();
+        }
+        Some(min_idx)
+    }
+
+    /// Returns the number of options covering the MRV-selected first item,
+    /// i.e. the number of parallel tasks a top-level fan-out (like
+    /// [collect_parallel_sorted](Solver::collect_parallel_sorted)) would
+    /// create at the root of the search tree
+    ///
+    /// Useful for a parallel driver deciding whether spreading the root
+    /// split across threads is worth it at all: a branching factor of 2
+    /// barely amortizes the overhead, while 300 clearly does. Returns `0`
+    /// if no mandatory item remains uncovered (the current state is
+    /// already a solution, with nothing left to branch on).
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[1])
+    ///     .add_option("o3", &[1, 2])
+    ///     .add_option("o4", &[2]);
+    ///
+    /// // Item 2 has the fewest covering options (o3, o4: 2), beating
+    /// // item 1's three (o1, o2, o3)
+    /// assert_eq!(s.root_branching_factor(), 2);
+    /// ```
+    pub fn root_branching_factor(&self) -> usize {
+        self.root_mrv_item()
+            .map(|idx| self.elements[idx].get_l())
+            .unwrap_or(0)
+    }
+
+    /// Enumerates every solution by fanning out across the options covering
+    /// the first branching item (chosen with the same MRV heuristic as
+    /// sequential iteration), solving each resulting subtree on a `rayon`
+    /// thread, and then concatenating the per-branch solutions back in
+    /// branch order.
+    ///
+    /// This is equivalent to `self.clone().collect::<Vec<_>>()`, just
+    /// computed with the top level of the search tree spread across
+    /// threads: the result is identical to sequential enumeration, branch
+    /// for branch, even though the branches themselves run concurrently.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(4);
+    /// s.add_option("o1", &[1, 2])
+    ///     .add_option("o2", &[3])
+    ///     .add_option("o3", &[2, 4])
+    ///     .add_option("o4", &[1]);
+    ///
+    /// assert_eq!(s.collect_parallel_sorted(), s.clone().collect::<Vec<_>>());
+    /// ```
+    pub fn collect_parallel_sorted(&self) -> Vec<Vec<String>>
+    where
+        M: Clone + Send + Sync,
+    {
+        // X3: find the item with the fewest remaining options, exactly as
+        // sequential iteration would at the very first step
+        let Some(min_idx) = self.root_mrv_item() else {
+            // No mandatory items left uncovered: the current state is
+            // already a (possibly empty) solution, with nothing left to
+            // branch on
+            return vec![self.output()];
+        };
+
+        // Walk the branches for min_idx in the same order x5/x6 would try
+        // them, recording the option name for each
+        let mut branches = Vec::new();
+        let mut p = self.elements[min_idx].d();
+        while p != min_idx {
+            let spacer = self.spacer_for(p);
+            branches.push(self.names[self.spacer_ids[&spacer]].clone());
+            p = self.elements[p].d();
+        }
+
+        branches
+            .par_iter()
+            .map(|name| {
+                let mut branch = self.clone();
+                branch.select(name).unwrap();
+                branch
+                    .map(|mut sol| {
+                        sol.insert(0, name.to_string());
+                        sol
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Counts solutions in parallel the same way [collect_parallel_sorted]
+    /// (Solver::collect_parallel_sorted) enumerates them, but stops early
+    /// once `limit` is reached instead of enumerating every solution
+    ///
+    /// Each top-level branch shares an atomic counter and checks it between
+    /// solutions, so once some branch pushes the total past `limit` the
+    /// others wind down too (already in-flight branches may still overshoot
+    /// briefly, since there's no hard synchronization between them). The
+    /// returned count is always clamped to `min(actual, limit)`, so the
+    /// result is deterministic regardless of how far any one branch
+    /// overshot before noticing.
+    ///
+    /// Useful for uniqueness-style checks on large problems (`limit == 2`:
+    /// "is this solution unique?") where both the parallelism and the
+    /// short-circuit matter.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(1);
+    /// s.add_option("o1", &[1]).add_option("o2", &[1]).add_option("o3", &[1]);
+    ///
+    /// assert_eq!(s.count_up_to_parallel(2), 2);
+    /// assert_eq!(s.count_up_to_parallel(10), 3);
+    /// ```
+    pub fn count_up_to_parallel(&self, limit: usize) -> usize
+    where
+        M: Clone + Send + Sync,
+    {
+        if limit == 0 {
+            return 0;
+        }
+
+        // X3: find the item with the fewest remaining options, exactly as
+        // collect_parallel_sorted does for its top-level fan-out
+        let Some(min_idx) = self.root_mrv_item() else {
+            return 1.min(limit);
+        };
+
+        let mut branches = Vec::new();
+        let mut p = self.elements[min_idx].d();
+        while p != min_idx {
+            let spacer = self.spacer_for(p);
+            branches.push(self.names[self.spacer_ids[&spacer]].clone());
+            p = self.elements[p].d();
+        }
+
+        let counter = std::sync::atomic::AtomicUsize::new(0);
+        branches.par_iter().for_each(|name| {
+            if counter.load(std::sync::atomic::Ordering::Relaxed) >= limit {
+                return;
+            }
+            let mut branch = self.clone();
+            branch.select(name).unwrap();
+            for _ in branch {
+                if counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1 >= limit {
+                    break;
+                }
+            }
+        });
+
+        counter.load(std::sync::atomic::Ordering::Relaxed).min(limit)
+    }
+
+    /// Exhausts the search and serializes every solution to a JSON array of
+    /// arrays of option names
+    ///
+    /// This is eager: it calls `self.by_ref().collect()` before handing the
+    /// result to `serde_json`, so the full solution set is held in memory at
+    /// once rather than streamed. For problem families where that's too
+    /// much to hold, iterate with [next](Solver::next) or
+    /// [solutions_with_indices](Solver::solutions_with_indices) and
+    /// serialize each solution as it's produced instead.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(1);
+    /// s.add_option("o1", &[1]).add_option("o2", &[1]);
+    ///
+    /// let json = s.solutions_json();
+    /// let parsed: Vec<Vec<String>> = serde_json::from_str(&json).unwrap();
+    /// assert_eq!(parsed, vec![vec!["o1".to_string()], vec!["o2".to_string()]]);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn solutions_json(&mut self) -> String {
+        let solutions: Vec<Vec<String>> = self.by_ref().collect();
+        serde_json::to_string(&solutions).expect("Vec<Vec<String>> always serializes")
+    }
+
+    /// Serializes [checkpoint](Solver::checkpoint)'s cursor to a JSON
+    /// string, for storing a paused search across a process restart
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("a1", &[1])
+    ///     .add_option("a2", &[1])
+    ///     .add_option("b", &[2]);
+    /// s.next();
+    ///
+    /// let json = s.checkpoint_json();
+    /// let mut resumed: Solver = Solver::new(2);
+    /// resumed
+    ///     .add_option("a1", &[1])
+    ///     .add_option("a2", &[1])
+    ///     .add_option("b", &[2]);
+    /// resumed.resume_from_json(&json).unwrap();
+    ///
+    /// assert_eq!(resumed.next(), s.next());
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn checkpoint_json(&self) -> String {
+        let cursor = self.checkpoint();
+        serde_json::to_string(&(cursor.l, &cursor.committed))
+            .expect("(usize, Vec<String>) always serializes")
+    }
+
+    /// Parses a [SearchCursor] from JSON produced by
+    /// [checkpoint_json](Solver::checkpoint_json) and [resumes](Solver::resume)
+    /// the search from it
+    #[cfg(feature = "serde")]
+    pub fn resume_from_json(&mut self, json: &str) -> Result<(), SolverError> {
+        let (l, committed): (usize, Vec<String>) = serde_json::from_str(json)
+            .map_err(|e| SolverError::MalformedInput(e.to_string()))?;
+        self.resume(SearchCursor { l, committed })
+    }
+
+}
+
+impl Solver<(usize, usize)> {
+    /// Returns a solver set up as a perfect-matching search over an
+    /// undirected graph on `num_vertices` vertices (numbered `1..=num_vertices`
+    /// to line up with the 1-indexed item convention used throughout this
+    /// crate), with one option per entry in `edges`
+    ///
+    /// Each vertex becomes a mandatory item, and each edge an option
+    /// covering its two endpoints -- a solution is then exactly a set of
+    /// edges covering every vertex exactly once, i.e. a perfect matching.
+    /// The edge's own `(usize, usize)` endpoints are attached as metadata
+    /// (see [add_option_with_meta](Solver::add_option_with_meta)), so a
+    /// caller doesn't have to re-parse the generated `"u-v"` option names
+    /// to recover them.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// // A 4-cycle: 1-2-3-4-1, which has exactly 2 perfect matchings
+    /// let edges = [(1, 2), (2, 3), (3, 4), (4, 1)];
+    /// let s = Solver::perfect_matching(&edges, 4);
+    /// assert_eq!(s.count(), 2);
+    /// ```
+    pub fn perfect_matching(edges: &[(usize, usize)], num_vertices: usize) -> Self {
+        let mut solver = Self::new(num_vertices);
+        for &(u, v) in edges {
+            let name = format!("{u}-{v}");
+            solver.add_option_with_meta(&name, &[u, v], (u, v));
+        }
+        solver
+    }
+}
+
+/// Splits a `self.options` row's `(item, color)` pairs back into the
+/// `items`/`colored` shape [add_option_colored](Solver::add_option_colored)
+/// takes, so a rebuild can round-trip colors without re-deriving them
+fn split_colored_option(option: &[ColoredItem]) -> (Vec<Index>, Vec<(Index, u32)>) {
+    let plain = option
+        .iter()
+        .filter(|&&(_, color)| color.is_none())
+        .map(|&(item, _)| item)
+        .collect();
+    let colored = option
+        .iter()
+        .filter_map(|&(item, color)| color.map(|c| (item, c)))
+        .collect();
+    (plain, colored)
+}
+
+impl<M: Clone> Solver<M> {
+    /// Appends `other`'s options into `self`, remapping `other`'s item
+    /// indices by adding `item_offset` to each (pass `0` to share items
+    /// directly, when the two solvers were built over the same item space)
+    ///
+    /// This supports modular problem construction: build a base problem,
+    /// then merge in variant-specific options. Must be called before
+    /// iteration begins on `self`. Any [colors](Solver::add_option_colored)
+    /// `other`'s options claimed on their items carry over unchanged.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut base: Solver = Solver::new(4);
+    /// base.add_option("o1", &[1, 2]);
+    ///
+    /// let mut extra: Solver = Solver::new(2);
+    /// extra.add_option("o2", &[1, 2]);
+    ///
+    /// // Items 1,2 of `extra` become items 3,4 of `base`
+    /// base.merge(&extra, 2).unwrap();
+    /// base.add_option("o3", &[3, 4]);
+    ///
+    /// assert_eq!(base.next(), Some(vec![String::from("o1"), String::from("o2")]));
+    /// ```
+    pub fn merge(&mut self, other: &Solver<M>, item_offset: Index) -> Result<(), SolverError> {
+        if self.started {
+            return Err(SolverError::AlreadyIterating);
+        }
+
+        for (id, name) in other.names.iter().enumerate() {
+            let other_items = other
+                .spacer_ids
+                .iter()
+                .find(|(_, &oid)| oid == id)
+                .map(|(spacer, _)| &other.options[spacer])
+                .expect("names and spacer_ids are always built together in add_option");
+
+            let mut mapped = Vec::with_capacity(other_items.len());
+            for &(item, color) in other_items {
+                let new_item = item + item_offset;
+                if new_item == 0 || new_item > self.items {
+                    return Err(SolverError::ItemOutOfRange(new_item));
+                }
+                mapped.push((new_item, color));
+            }
+
+            let (plain, colored) = split_colored_option(&mapped);
+            self.add_option_impl_colored(name, &plain, &colored, other.meta[id].clone());
+        }
+
+        Ok(())
+    }
+
+    /// Adds an "exactly `k`" cardinality constraint over the options named
+    /// in `option_names`: any solution will contain precisely `k` of them
+    ///
+    /// This works as a modeling gadget: `k` new mandatory "slot" items are
+    /// introduced, and each named option is rebuilt as `k` variant copies
+    /// (one per slot, each covering the original items plus that slot). The
+    /// un-gadgeted original row is discarded, so a named option can now only
+    /// appear in a solution by covering one slot. Since the `k` slots are
+    /// themselves mandatory items, exactly `k` of them are covered in any
+    /// solution, and therefore exactly `k` of the named options appear.
+    ///
+    /// Because this rebuilds the internal option matrix from scratch, it
+    /// must be called before iteration begins, and all other options are
+    /// preserved unchanged (beyond having their item indices shifted to make
+    /// room for the new slot items).
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[2])
+    ///     .add_option("o3", &[1])
+    ///     .add_option("o4", &[2])
+    ///     .add_option("o5", &[1, 2]);
+    ///
+    /// s.add_cardinality(&["o1", "o2", "o3", "o4"], 2).unwrap();
+    ///
+    /// for sol in s {
+    ///     let named = ["o1", "o2", "o3", "o4"];
+    ///     let count = sol.iter().filter(|n| named.contains(&n.as_str())).count();
+    ///     assert_eq!(count, 2);
+    /// }
+    /// ```
+    pub fn add_cardinality(&mut self, option_names: &[&str], k: usize) -> Result<(), SolverError> {
+        if self.started {
+            return Err(SolverError::AlreadyIterating);
+        }
+        for &name in option_names {
+            if !self.names.iter().any(|n| n.as_ref() == name) {
+                return Err(SolverError::UnknownOption(name.to_string()));
+            }
+        }
+
+        // Gather all existing options, in original insertion order, before rebuilding
+        let root_spacer = self.items + 1;
+        let mut all_options: Vec<(String, Vec<ColoredItem>, Option<M>)> = vec![];
+        let mut spacer = self.elements[root_spacer].d();
+        while spacer != root_spacer {
+            let id = self.spacer_ids[&spacer];
+            all_options.push((
+                self.names[id].to_string(),
+                self.options[&spacer].clone(),
+                self.meta[id].clone(),
+            ));
+            spacer = self.elements[spacer].d();
+        }
+
+        let old_optional = self.optional;
+        let new_items = self.items + k;
+        let new_mandatory = old_optional + k - 1;
+        let counters: Vec<Index> = (old_optional..old_optional + k).collect();
+        let shift = |item: Index| -> Index {
+            if item >= old_optional {
+                item + k
+            } else {
+                item
+            }
+        };
+
+        *self = Solver::new_optional(new_mandatory, new_items - new_mandatory);
+
+        for (name, items, meta) in all_options {
+            let shifted: Vec<ColoredItem> = items
+                .iter()
+                .map(|&(i, color)| (shift(i), color))
+                .collect();
+
+            if option_names.contains(&name.as_str()) {
+                for &counter in &counters {
+                    let mut row = shifted.clone();
+                    row.push((counter, None));
+                    let (plain, colored) = split_colored_option(&row);
+                    self.add_option_impl_colored(&name, &plain, &colored, meta.clone());
+                }
+            } else {
+                let (plain, colored) = split_colored_option(&shifted);
+                self.add_option_impl_colored(&name, &plain, &colored, meta);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Makes the named options mutually exclusive: any solution contains
+    /// at most one of `members`
+    ///
+    /// This works by introducing a single new optional item shared by
+    /// every member option and splicing it into each of their rows.
+    /// Optional items may be covered at most once (see
+    /// [new_optional](Solver::new_optional)), so selecting one member
+    /// hides every other member from that point on, exactly as if they'd
+    /// conflicted directly. All other options are preserved unchanged.
+    ///
+    /// Because this rebuilds the internal option matrix, it must be called
+    /// before iteration begins.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(1);
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[1])
+    ///     .add_option("o3", &[1]);
+    ///
+    /// s.add_exclusion_group(&["o1", "o2"]).unwrap();
+    ///
+    /// for sol in s {
+    ///     let in_group = sol.iter().filter(|n| ["o1", "o2"].contains(&n.as_str())).count();
+    ///     assert!(in_group <= 1);
+    /// }
+    /// ```
+    pub fn add_exclusion_group(&mut self, members: &[&str]) -> Result<(), SolverError> {
+        if self.started {
+            return Err(SolverError::AlreadyIterating);
+        }
+        for &name in members {
+            if !self.names.iter().any(|n| n.as_ref() == name) {
+                return Err(SolverError::UnknownOption(name.to_string()));
+            }
+        }
+
+        // Gather all existing options, in original insertion order, before rebuilding
+        let root_spacer = self.items + 1;
+        let mut all_options: Vec<(String, Vec<ColoredItem>, Option<M>)> = vec![];
+        let mut spacer = self.elements[root_spacer].d();
+        while spacer != root_spacer {
+            let id = self.spacer_ids[&spacer];
+            all_options.push((
+                self.names[id].to_string(),
+                self.options[&spacer].clone(),
+                self.meta[id].clone(),
+            ));
+            spacer = self.elements[spacer].d();
+        }
+
+        // The new group item is appended after every existing item, so no
+        // other item's index needs to shift
+        let mandatory = self.optional - 1;
+        let old_optional_count = self.items - mandatory;
+        let group_item = self.items + 1;
+
+        *self = Solver::new_optional(mandatory, old_optional_count + 1);
+
+        for (name, mut items, meta) in all_options {
+            if members.contains(&name.as_str()) {
+                items.push((group_item, None));
+            }
+            let (plain, colored) = split_colored_option(&items);
+            self.add_option_impl_colored(&name, &plain, &colored, meta);
+        }
+
+        Ok(())
+    }
+
+    /// Makes options `a` and `b` mutually exclusive: no solution contains
+    /// both
+    ///
+    /// A common side-constraint in scheduling/configuration problems --
+    /// "these two choices conflict, even though they don't share an item"
+    /// -- that pure exact cover can't express directly. A thin, two-member
+    /// special case of [add_exclusion_group](Solver::add_exclusion_group),
+    /// which does the actual work of introducing the shared optional item.
+    ///
+    /// Because this rebuilds the internal option matrix, it must be called
+    /// before iteration begins.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(1);
+    /// s.add_option("o1", &[1])
+    ///     .add_option("o2", &[1])
+    ///     .add_option("o3", &[1]);
+    ///
+    /// s.forbid_pair("o1", "o2").unwrap();
+    ///
+    /// for sol in s {
+    ///     assert!(!(sol.contains(&"o1".to_string()) && sol.contains(&"o2".to_string())));
+    /// }
+    /// ```
+    pub fn forbid_pair(&mut self, a: &str, b: &str) -> Result<(), SolverError> {
+        self.add_exclusion_group(&[a, b])
+    }
+
+    /// Makes `item` covered whenever `triggering_option` is chosen, by
+    /// adding it to that option's row
+    ///
+    /// Models the common derived-constraint pattern "this item is covered
+    /// iff option X is chosen" -- e.g. an optional item marking some
+    /// consequence of a choice -- without requiring every caller to list
+    /// `item` by hand whenever they build `triggering_option`. Only
+    /// `triggering_option`'s coverage changes; every other option is
+    /// preserved unchanged.
+    ///
+    /// Because this rebuilds the internal option matrix (see
+    /// [add_exclusion_group](Solver::add_exclusion_group)), it must be
+    /// called before iteration begins.
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new_optional(1, 1);
+    /// s.add_option("o1", &[1]).add_option("o2", &[1]);
+    /// s.link_implied_item(2, "o1").unwrap();
+    ///
+    /// s.next();
+    /// assert_eq!(s.output(), vec!["o1".to_string()]);
+    /// assert!(s.solution_coverage().contains(&2));
+    ///
+    /// s.next();
+    /// assert_eq!(s.output(), vec!["o2".to_string()]);
+    /// assert!(!s.solution_coverage().contains(&2));
+    /// ```
+    pub fn link_implied_item(&mut self, item: Index, triggering_option: &str) -> Result<(), SolverError> {
+        if self.started {
+            return Err(SolverError::AlreadyIterating);
+        }
+        if !self.names.iter().any(|n| n.as_ref() == triggering_option) {
+            return Err(SolverError::UnknownOption(triggering_option.to_string()));
+        }
+
+        // Gather all existing options, in original insertion order, before rebuilding
+        let root_spacer = self.items + 1;
+        let mut all_options: Vec<(String, Vec<ColoredItem>, Option<M>)> = vec![];
+        let mut spacer = self.elements[root_spacer].d();
+        while spacer != root_spacer {
+            let id = self.spacer_ids[&spacer];
+            all_options.push((
+                self.names[id].to_string(),
+                self.options[&spacer].clone(),
+                self.meta[id].clone(),
+            ));
+            spacer = self.elements[spacer].d();
+        }
+
+        let mandatory = self.optional - 1;
+        let optional_count = self.items - mandatory;
+
+        *self = Solver::new_optional(mandatory, optional_count);
+
+        for (name, mut items, meta) in all_options {
+            if name == triggering_option && !items.iter().any(|&(i, _)| i == item) {
+                items.push((item, None));
+            }
+            let (plain, colored) = split_colored_option(&items);
+            self.add_option_impl_colored(&name, &plain, &colored, meta);
+        }
+
+        Ok(())
+    }
+
+    /// Returns an [OwnedNames] iterator over this solver's remaining
+    /// solutions, yielding `Vec<Arc<str>>` instead of [Iterator]'s
+    /// `Vec<String>`
+    ///
+    /// [Iterator::next] (built on [output](Solver::output)) allocates a
+    /// fresh `String` for every option name in every solution. Since
+    /// `self.names` already stores `Arc<str>`, cloning those instead is
+    /// just a refcount bump -- a real win when the same small set of
+    /// option names recurs across many solutions, as in enumerating a
+    /// Sudoku or an Aztec diamond tiling. Use this when a caller wants to
+    /// keep the names owned (unlike [solution_slices](Solver::solution_slices),
+    /// which trades names for bare indices); keep using plain `Iterator`
+    /// when the small number of solutions makes the difference moot.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///# use std::sync::Arc;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2]);
+    ///
+    /// let mut owned = s.into_iter_owned_names();
+    /// assert_eq!(
+    ///     owned.next(),
+    ///     Some(vec![Arc::from("o1"), Arc::from("o2")])
+    /// );
+    /// assert_eq!(owned.next(), None);
+    /// ```
+    pub fn into_iter_owned_names(&mut self) -> OwnedNames<'_, M> {
+        OwnedNames { solver: self }
+    }
+
+    /// Returns a [SolutionSlices] streaming iterator over this solver's
+    /// remaining solutions
+    ///
+    /// Unlike [Iterator::next], which allocates a fresh `Vec<String>` name
+    /// list per solution, [SolutionSlices::next] writes each solution's
+    /// option indices into a single buffer it reuses across calls, so
+    /// consuming many solutions does no per-solution allocation. This
+    /// trades names for indices (see [output_indices](Solver::output_indices)
+    /// for what they mean) -- use it when a hot loop only needs to count,
+    /// sum, or otherwise fold over solutions rather than display them
+    ///
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1]).add_option("o2", &[2]);
+    ///
+    /// let mut slices = s.solution_slices();
+    /// assert_eq!(slices.next(), Some(&[0, 1][..]));
+    /// assert_eq!(slices.next(), None);
+    /// ```
+    pub fn solution_slices(&mut self) -> SolutionSlices<'_, M> {
+        SolutionSlices {
+            solver: self,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Reports whether this solver has at least one solution, without
+    /// consuming it
+    ///
+    /// Works on a clone, so `self` is left untouched (in particular,
+    /// still not [started](SolverError::AlreadyIterating)) regardless of
+    /// the answer.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(1);
+    /// s.add_option("o1", &[1]);
+    /// assert!(s.is_satisfiable());
+    /// assert!(s.next().is_some()); // untouched by the probe above
+    /// ```
+    pub fn is_satisfiable(&self) -> bool {
+        self.clone().next().is_some()
+    }
+
+    /// Reports whether the named option can participate in at least one
+    /// solution: clones the solver, [selects](Solver::select) `name` on
+    /// the clone, and checks [is_satisfiable](Solver::is_satisfiable)
+    ///
+    /// Useful for look-ahead/hint generation: for a Sudoku this identifies
+    /// which candidate digits for a cell are actually achievable, rather
+    /// than merely not yet ruled out by the immediate row/column/box
+    /// constraints.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("only", &[1, 2]).add_option("conflicting", &[1]);
+    ///
+    /// // "only" is the unique solution; "conflicting" covers item 1 but
+    /// // leaves item 2 uncoverable by anything else
+    /// assert!(s.probe_option("only"));
+    /// assert!(!s.probe_option("conflicting"));
+    /// ```
+    pub fn probe_option(&self, name: &str) -> bool {
+        let mut probe = self.clone();
+        probe.select(name).is_ok() && probe.is_satisfiable()
+    }
+
+    /// Returns every option name that [probe_option](Solver::probe_option)
+    /// accepts: the options that can participate in at least one solution
+    ///
+    /// This clones and searches once per option, so it's `O(options)`
+    /// times the cost of a single search -- a powerful analysis primitive,
+    /// but a potentially expensive one on large problems.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("o1", &[1, 2])
+    ///     .add_option("o2", &[1])
+    ///     .add_option("o3", &[2]);
+    ///
+    /// // o1 alone solves it; o2+o3 together also work; none is impossible
+    /// assert_eq!(s.feasible_options(), vec!["o1", "o2", "o3"]);
+    /// ```
+    pub fn feasible_options(&self) -> Vec<String> {
+        self.names
+            .iter()
+            .filter(|name| self.probe_option(name))
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Deletes every option that [probe_option](Solver::probe_option)
+    /// rejects -- i.e. every option that cannot appear in any solution --
+    /// from the problem entirely, returning how many were pruned
+    ///
+    /// A preprocessing pass rather than a search-time optimization: since
+    /// an unreachable option is, by definition, never part of any
+    /// solution, removing it changes nothing about the set of solutions or
+    /// their count, only how much of the search space has to be walked to
+    /// find them. For a heavily-constrained problem (e.g. a Sudoku with
+    /// many givens) this can shrink the option count dramatically before
+    /// the main enumeration even starts. Like [constrain_item](Solver::constrain_item),
+    /// this is `O(options)` probes, each a full clone-and-search, so it's
+    /// itself not free -- worth it when it's paid once to make every
+    /// subsequent search on the same solver faster.
+    ///
+    /// Must be called before iteration begins, since (like [select](Solver::select))
+    /// it mutates the links directly rather than going through the search
+    /// stages.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("only", &[1, 2]).add_option("conflicting", &[1]);
+    ///
+    /// // "conflicting" covers item 1 but leaves item 2 uncoverable by
+    /// // anything else, so it can never appear in a full solution
+    /// assert_eq!(s.prune_unreachable_options(), Ok(1));
+    /// assert_eq!(s.next(), Some(vec!["only".to_string()]));
+    /// assert_eq!(s.next(), None);
+    /// ```
+    pub fn prune_unreachable_options(&mut self) -> Result<usize, SolverError> {
+        if self.started {
+            return Err(SolverError::AlreadyIterating);
+        }
+
+        let unreachable: Vec<Index> = self
+            .spacer_ids
+            .iter()
+            .filter(|(_, &id)| !self.probe_option(&self.names[id]))
+            .map(|(&spacer, _)| spacer)
+            .collect();
+
+        let pruned = unreachable.len();
+        for spacer in unreachable {
+            self.delete_option_row(spacer);
+        }
+
+        Ok(pruned)
+    }
+
+    /// Returns a clone of this solver with the named option deleted from
+    /// the problem entirely, via [delete_option_row](Solver::delete_option_row)
+    ///
+    /// Used by [backbone_options](Solver::backbone_options) to test whether
+    /// an option is load-bearing, without disturbing `self`.
+    fn forbid_option(&self, name: &str) -> Result<Solver<M>, SolverError> {
+        let mut forbidden = self.clone();
+        let (&spacer, _) = forbidden
+            .spacer_ids
+            .iter()
+            .find(|(_, &id)| forbidden.names[id].as_ref() == name)
+            .ok_or_else(|| SolverError::UnknownOption(name.to_string()))?;
+
+        forbidden.delete_option_row(spacer);
+        Ok(forbidden)
+    }
+
+    /// Returns every "backbone" option: one whose removal from the problem
+    /// makes it unsatisfiable, meaning no solution exists without it
+    ///
+    /// Builds on [forbid_option](Solver::forbid_option) and
+    /// [is_satisfiable](Solver::is_satisfiable), costing one clone and
+    /// search per option, much like [feasible_options](Solver::feasible_options).
+    /// Note that this is not the same question as "does every solution
+    /// contain this option" -- an option can be load-bearing for
+    /// satisfiability without appearing in every individual solution, if
+    /// removing it also removes an item that only it could ever cover.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(2);
+    /// s.add_option("only", &[1, 2]).add_option("extra", &[1]);
+    ///
+    /// // "extra" alone can't cover item 2, so "only" is the sole way to
+    /// // solve the problem; removing "extra" still leaves "only" to do it
+    /// assert_eq!(s.backbone_options(), vec!["only"]);
+    /// ```
+    pub fn backbone_options(&self) -> Vec<String> {
+        self.names
+            .iter()
+            .filter(|name| {
+                self.forbid_option(name)
+                    .map(|forbidden| !forbidden.is_satisfiable())
+                    .unwrap_or(false)
+            })
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Structurally compares this solver's problem against another's,
+    /// reporting every item-count and option difference rather than a bare
+    /// `bool`
+    ///
+    /// Options are matched up by the (sorted) set of items they cover, not
+    /// by name, since two constraint generators can reasonably name
+    /// equivalent options differently. Built on
+    /// [into_problem_description](Solver::into_problem_description), so it's
+    /// a debugging aid for anyone writing a custom constraint generator --
+    /// e.g. checking that [Sudoku::new_rect](crate::sudoku::Sudoku::new_rect)
+    /// really does produce the same constraint matrix as an equivalent
+    /// hand-built [new_with_regions](crate::sudoku::Sudoku::new_with_regions)
+    /// call -- rather than something meant to run on a search hot path.
+    /// ```
+    ///# use dlx_rs::solver::{Solver, ProblemDiff};
+    ///
+    /// let mut a: Solver = Solver::new(3);
+    /// a.add_option("o1", &[1, 2]).add_option("o2", &[3]);
+    ///
+    /// let mut b: Solver = Solver::new(3);
+    /// b.add_option("o1", &[1, 2]).add_option("o2", &[2, 3]);
+    ///
+    /// assert_eq!(
+    ///     a.diff_problems(&b),
+    ///     vec![
+    ///         ProblemDiff::OnlyInThis { name: "o2".to_string(), items: vec![3] },
+    ///         ProblemDiff::OnlyInOther { name: "o2".to_string(), items: vec![2, 3] },
+    ///     ]
+    /// );
+    /// assert!(a.diff_problems(&a.clone()).is_empty());
+    /// ```
+    pub fn diff_problems(&self, other: &Solver<M>) -> Vec<ProblemDiff> {
+        let this = self.clone().into_problem_description();
+        let that = other.clone().into_problem_description();
+
+        let mut diffs = Vec::new();
+        if this.num_items != that.num_items {
+            diffs.push(ProblemDiff::ItemCountMismatch {
+                this: this.num_items,
+                other: that.num_items,
+            });
+        }
+        if this.num_optional != that.num_optional {
+            diffs.push(ProblemDiff::OptionalCountMismatch {
+                this: this.num_optional,
+                other: that.num_optional,
+            });
+        }
+
+        // Matching is color-aware (two options only count as equivalent if
+        // they claim the same color on the same items), but the reported
+        // `items` stay plain indices, since `ProblemDiff` predates colored
+        // items and callers already match on bare item lists
+        let sorted = |items: &[ColoredItem]| {
+            let mut items = items.to_vec();
+            items.sort_unstable();
+            items
+        };
+        let plain = |items: &[ColoredItem]| -> Vec<Index> {
+            items.iter().map(|&(item, _)| item).collect()
+        };
+
+        let mut remaining_other: Vec<(String, Vec<ColoredItem>)> = that
+            .options
+            .iter()
+            .map(|(name, items)| (name.clone(), sorted(items)))
+            .collect();
+
+        for (name, items) in &this.options {
+            let items = sorted(items);
+            match remaining_other
+                .iter()
+                .position(|(_, other_items)| *other_items == items)
+            {
+                Some(pos) => {
+                    remaining_other.remove(pos);
+                }
+                None => diffs.push(ProblemDiff::OnlyInThis {
+                    name: name.clone(),
+                    items: plain(&items),
+                }),
+            }
+        }
+
+        for (name, items) in remaining_other {
+            diffs.push(ProblemDiff::OnlyInOther {
+                name,
+                items: plain(&items),
+            });
+        }
+
+        diffs
+    }
+
+    /// Solves the minimum set cover problem over this solver's mandatory
+    /// items (see [num_mandatory](Solver::num_mandatory)), a different
+    /// problem from the exact cover that [solve](Solver::solve) performs
+    ///
+    /// An option may be chosen even though one of its items is already
+    /// covered by another chosen option -- covering an item here doesn't
+    /// forbid other options from also covering it, it just stops counting
+    /// towards "are we done yet". That means the dancing-links structure
+    /// (built around removing an item from consideration once it's
+    /// covered) doesn't apply, so this runs its own branch-and-bound over
+    /// `self.options` instead: repeatedly branch on whichever uncovered
+    /// item has the fewest remaining candidate options (the same MRV
+    /// heuristic the X3 stage uses, for the same reason -- it narrows the
+    /// search fastest), pruning any branch that has already used at least
+    /// as many options as the best cover found so far. Returns `None` if
+    /// some mandatory item isn't covered by any option at all.
+    /// ```
+    ///# use dlx_rs::solver::Solver;
+    ///
+    /// let mut s: Solver = Solver::new(4);
+    /// s.add_option("o1", &[1, 2, 3])
+    ///     .add_option("o2", &[2, 4])
+    ///     .add_option("o3", &[4])
+    ///     .add_option("o4", &[1, 2, 3, 4]);
+    ///
+    /// // o4 alone covers every item, and nothing can do better than 1 option
+    /// assert_eq!(s.min_set_cover(), Some(vec!["o4".to_string()]));
+    /// ```
+    pub fn min_set_cover(&self) -> Option<Vec<String>> {
+        let mandatory = self.num_mandatory();
+
+        let mut by_insertion_order: Vec<(usize, Vec<Index>)> = self
+            .spacer_ids
+            .iter()
+            .map(|(spacer, &idx)| {
+                let items = self.options[spacer].iter().map(|&(item, _)| item).collect();
+                (idx, items)
+            })
+            .collect();
+        by_insertion_order.sort_by_key(|&(idx, _)| idx);
+        let option_items: Vec<Vec<Index>> = by_insertion_order
+            .into_iter()
+            .map(|(_, items)| items)
+            .collect();
+
+        let mut item_to_options: HashMap<Index, Vec<usize>> = HashMap::new();
+        for (opt_idx, items) in option_items.iter().enumerate() {
+            for &item in items.iter() {
+                if item >= 1 && item <= mandatory {
+                    item_to_options.entry(item).or_default().push(opt_idx);
+                }
+            }
+        }
+        if (1..=mandatory).any(|item| !item_to_options.contains_key(&item)) {
+            return None;
+        }
+
+        let mut uncovered: Vec<Index> = (1..=mandatory).collect();
+        let mut chosen = Vec::new();
+        let mut best: Option<Vec<usize>> = None;
+        min_set_cover_search(
+            &mut uncovered,
+            &mut chosen,
+            &mut best,
+            &item_to_options,
+            &option_items,
+        );
+
+        best.map(|indices| {
+            indices
+                .into_iter()
+                .map(|idx| self.names[idx].to_string())
+                .collect()
+        })
+    }
+}
+
+/// Recursive branch-and-bound helper for [min_set_cover](Solver::min_set_cover)
+///
+/// A free function rather than a method since it only needs the item/option
+/// maps, not `self` -- and recursing through `&self` methods would require
+/// threading the same borrows through every call anyway.
+fn min_set_cover_search(
+    uncovered: &mut Vec<Index>,
+    chosen: &mut Vec<usize>,
+    best: &mut Option<Vec<usize>>,
+    item_to_options: &HashMap<Index, Vec<usize>>,
+    option_items: &[Vec<Index>],
+) {
+    if uncovered.is_empty() {
+        if best.as_ref().is_none_or(|b| chosen.len() < b.len()) {
+            *best = Some(chosen.clone());
+        }
+        return;
+    }
+    if let Some(b) = best {
+        if chosen.len() + 1 >= b.len() {
+            return;
+        }
+    }
+
+    let &item = uncovered
+        .iter()
+        .min_by_key(|it| item_to_options[it].len())
+        .expect("uncovered is non-empty");
+    for &opt_idx in &item_to_options[&item] {
+        let newly_covered: Vec<Index> = option_items[opt_idx]
+            .iter()
+            .copied()
+            .filter(|it| uncovered.contains(it))
+            .collect();
+        uncovered.retain(|it| !newly_covered.contains(it));
+        chosen.push(opt_idx);
+
+        min_set_cover_search(uncovered, chosen, best, item_to_options, option_items);
+
+        chosen.pop();
+        uncovered.extend(newly_covered);
+    }
+}
+
+/// An iterator over a [Solver]'s solutions, yielding `Vec<Arc<str>>`
+/// instead of [Iterator]'s `Vec<String>` (see
+/// [into_iter_owned_names](Solver::into_iter_owned_names))
+///
+/// Unlike [SolutionSlices], whose items borrow from the solver, each
+/// `Vec<Arc<str>>` here is independently owned (just sharing the
+/// underlying name storage), so this can implement `Iterator` directly.
+pub struct OwnedNames<'a, M = ()> {
+    solver: &'a mut Solver<M>,
+}
+
+impl<M> Iterator for OwnedNames<'_, M> {
+    type Item = Vec<Arc<str>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.solver.solve()?;
+        Some(self.solver.output_shared())
+    }
+}
+
+/// A streaming iterator over a [Solver]'s solutions, yielding `&[Index]`
+/// views into a reusable buffer (see [solution_slices](Solver::solution_slices))
+/// instead of allocating a `Vec` per solution
+///
+/// `Iterator` can't express this directly since its items would borrow
+/// from `self`, so this is a standalone type with its own `next` method;
+/// drive it with a `while let Some(indices) = slices.next()` loop rather
+/// than `for`
+pub struct SolutionSlices<'a, M = ()> {
+    solver: &'a mut Solver<M>,
+    buf: Vec<Index>,
+}
+
+impl<'a, M> SolutionSlices<'a, M> {
+    /// Advances to the next solution and returns a view of its option
+    /// indices (in [output_indices](Solver::output_indices) order), or
+    /// `None` once the search is exhausted
+    ///
+    /// Named `next` by analogy with [Iterator::next], but this type can't
+    /// implement `Iterator` itself since the slice it returns borrows from
+    /// `self`
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&[Index]> {
+        self.solver.solve()?;
+        self.buf.clear();
+        self.buf.extend(
+            self.solver
+                .sol_vec
+                .iter()
+                .take(self.solver.l)
+                .map(|&x| self.solver.spacer_for(x))
+                .map(|x| self.solver.spacer_ids[&x]),
+        );
+        Some(&self.buf)
+    }
+}
+
+/// RAII guard returned by [cover_scoped](Solver::cover_scoped): uncovers
+/// its item when dropped, restoring the solver to how it was before the
+/// guard was created
+///
+/// Derefs to the underlying [Solver] so the covered state can be inspected
+/// (or further searched into) while the guard is held.
+pub struct CoverGuard<'a, M = ()> {
+    solver: &'a mut Solver<M>,
+    item: Index,
+}
+
+impl<M> std::ops::Deref for CoverGuard<'_, M> {
+    type Target = Solver<M>;
+    fn deref(&self) -> &Solver<M> {
+        self.solver
+    }
+}
+
+impl<M> std::ops::DerefMut for CoverGuard<'_, M> {
+    fn deref_mut(&mut self) -> &mut Solver<M> {
+        self.solver
+    }
+}
+
+impl<M> Drop for CoverGuard<'_, M> {
+    fn drop(&mut self) {
+        let _ = self.solver.uncover(self.item);
+    }
+}
+
+/// Iterator returned by [events](Solver::events), yielding the search's
+/// [SearchEvent]s one at a time
+pub struct SearchEvents<'a, M = ()> {
+    solver: &'a mut Solver<M>,
+}
+
+impl<'a, M> Iterator for SearchEvents<'a, M> {
+    type Item = SearchEvent;
+
+    fn next(&mut self) -> Option<SearchEvent> {
+        loop {
+            if let Some(ev) = self
+                .solver
+                .event_queue
+                .as_mut()
+                .and_then(VecDeque::pop_front)
+            {
+                return Some(ev);
+            }
+            if matches!(self.solver.step(), StepOutcome::Exhausted) {
+                return None;
+            }
+        }
+    }
+}
+
+impl<M> Iterator for Solver<M> {
+    type Item = Vec<String>;
+    /// Produces next solution by following algorithm X
+    /// as described in tAoCP in Fasc 5c, Dancing Links, Knuth
+    ///
+    /// Returns `Some` containing a vector of items if a solution remains, or
+    /// `None` when no more solutions remaining
+    fn next(&mut self) -> Option<Self::Item> {
+        self.solve()
+    }
+}
+
+/// Once [x8](Solver::x8) reaches `self.l == 0` it returns `false` without
+/// touching `self.l` or `self.stage`, so every later call re-enters the same
+/// exhausted X8 state and `solve` keeps returning `None` for good
+impl<M> std::iter::FusedIterator for Solver<M> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn spacer_for() {
+        let mut s: Solver = Solver::new(4);
+        s.add_option("o1", &[1, 2])
+            .add_option("o2", &[2, 3])
+            .add_option("o3", &[3, 4])
+            .add_option("o4", &[1, 4]);
+
+        // This creates a vec which looks like
+        // [i0, i1, i2, i3, i4, s0
+        //      x    x          s1
+        //           x   x      s2
+        //               x   x  s3
+        //      x            x  s4]
+        //
+
+        let spacer_answers = HashMap::from([
+            (6, 8),
+            (7, 8),
+            (8, 8),
             (9, 11),
             (10, 11),
             (11, 11),
@@ -904,8 +5003,1256 @@ mod tests {
             (17, 17),
         ]);
 
-        for i in 6..=17 {
-            assert_eq!(s.spacer_for(i), spacer_answers[&i]);
+        for i in 6..=17 {
+            assert_eq!(s.spacer_for(i), spacer_answers[&i]);
+        }
+    }
+
+    #[test]
+    fn output_filtered_partitions_solution_by_prefix() {
+        let mut s: Solver = Solver::new(4);
+        s.add_option("Row:1", &[1, 3]).add_option("Colour:red", &[2, 4]);
+
+        let sol = s.next().unwrap();
+        assert_eq!(sol.len(), 2);
+
+        let rows = s.output_filtered("Row:");
+        let colours = s.output_filtered("Colour:");
+
+        assert_eq!(rows, vec!["Row:1".to_string()]);
+        assert_eq!(colours, vec!["Colour:red".to_string()]);
+        assert_eq!(rows.len() + colours.len(), sol.len());
+    }
+
+    #[test]
+    fn rotate_option_order() {
+        let mut s: Solver = Solver::new(3);
+        s.add_option("o1", &[1])
+            .add_option("o2", &[1])
+            .add_option("o3", &[2, 3]);
+
+        s.rotate_option_order(1).unwrap();
+        assert_eq!(vec!["o3", "o2"], s.next().unwrap());
+        assert_eq!(vec!["o3", "o1"], s.next().unwrap());
+        assert_eq!(None, s.next());
+    }
+
+    #[test]
+    fn rotate_option_order_after_iteration_started_errors() {
+        let mut s: Solver = Solver::new(3);
+        s.add_option("o1", &[1])
+            .add_option("o2", &[1])
+            .add_option("o3", &[2, 3]);
+
+        s.next();
+        assert_eq!(
+            s.rotate_option_order(1),
+            Err(SolverError::AlreadyIterating)
+        );
+    }
+
+    /// Every option here covers exactly one item -- the all-single-item-row
+    /// shape the `fast_single_item` feature targets. The solution set must
+    /// come out the same whether that feature is enabled or not; running
+    /// this test both with and without `--features fast_single_item` is
+    /// what actually confirms the fast path is transparent
+    #[test]
+    fn single_item_options_match_with_and_without_fast_path() {
+        let mut s: Solver = Solver::new(3);
+        s.add_option("1a", &[1])
+            .add_option("1b", &[1])
+            .add_option("2a", &[2])
+            .add_option("2b", &[2])
+            .add_option("3a", &[3]);
+
+        let solutions: Vec<Vec<String>> = s.collect();
+        assert_eq!(solutions.len(), 4);
+        for sol in &solutions {
+            assert_eq!(sol.len(), 3);
+        }
+    }
+
+    #[test]
+    fn solution_coverage_contains_every_mandatory_item_once() {
+        let mut s: Solver = Solver::new(4);
+        s.add_option("o1", &[1, 2])
+            .add_option("o2", &[3])
+            .add_option("o3", &[4]);
+
+        s.next().unwrap();
+
+        let mut coverage = s.solution_coverage();
+        coverage.sort();
+        assert_eq!(coverage, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn add_cardinality() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1])
+            .add_option("o2", &[2])
+            .add_option("o3", &[1])
+            .add_option("o4", &[2])
+            .add_option("o5", &[1, 2]);
+
+        // Without the constraint, "o5 alone" is also a valid solution
+        let unconstrained: Vec<Vec<String>> = s.clone().collect();
+        assert!(unconstrained.iter().any(|sol| sol == &vec!["o5".to_string()]));
+
+        s.add_cardinality(&["o1", "o2", "o3", "o4"], 2).unwrap();
+
+        let named = ["o1", "o2", "o3", "o4"];
+        let solutions: Vec<Vec<String>> = s.collect();
+        assert!(!solutions.is_empty());
+        for sol in &solutions {
+            let count = sol.iter().filter(|n| named.contains(&n.as_str())).count();
+            assert_eq!(count, 2);
+        }
+    }
+
+    #[test]
+    fn named_item_display() {
+        let mut s: Solver = Solver::new(3);
+        s.with_item_names(&["i1", "i2", "i3"]);
+        s.add_option("o1", &[1, 2]).add_option("o2", &[2, 3]);
+
+        assert_eq!(s.item_name(1), Some("i1"));
+        assert_eq!(s.item_name(2), Some("i2"));
+        assert_eq!(s.item_name(3), Some("i3"));
+
+        let rendered = s.to_string();
+        assert_eq!(rendered, " i1 i2 i3 \ni1i2\ni2  i3\n");
+    }
+
+    #[test]
+    fn new_with_item_names_matches_new_then_with_item_names() {
+        let mut built: Solver = Solver::new_with_item_names(&["i1", "i2", "i3"]);
+        built.add_option("o1", &[1, 2]).add_option("o2", &[2, 3]);
+
+        let mut separate: Solver = Solver::new(3);
+        separate.with_item_names(&["i1", "i2", "i3"]);
+        separate.add_option("o1", &[1, 2]).add_option("o2", &[2, 3]);
+
+        assert_eq!(built.to_string(), separate.to_string());
+        assert_eq!(built.item_name(1), Some("i1"));
+    }
+
+    #[test]
+    fn count_solutions_mod_agrees_with_the_exact_count() {
+        // 5 independent mandatory items, each with 2 interchangeable
+        // options: 2^5 = 32 solutions
+        let mut s: Solver = Solver::new(5);
+        for i in 1..=5 {
+            s.add_option(&format!("o{i}a"), &[i]);
+            s.add_option(&format!("o{i}b"), &[i]);
+        }
+
+        let exact = s.clone().count();
+        assert_eq!(exact, 32);
+
+        for modulus in [3u64, 7, 100] {
+            assert_eq!(s.clone().count_solutions_mod(modulus), exact as u64 % modulus);
+        }
+    }
+
+    #[test]
+    fn find_one_matches_the_first_next_and_leaves_the_solver_resumable() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1, 2])
+            .add_option("o2", &[1])
+            .add_option("o3", &[2]);
+
+        let mut via_next: Solver = s.clone();
+        assert_eq!(s.find_one(), via_next.next());
+        // The solver is left positioned to resume, just like next()
+        assert_eq!(s.next(), via_next.next());
+    }
+
+    #[test]
+    fn option_conflicts_lists_options_sharing_an_item() {
+        let mut s: Solver = Solver::new(3);
+        s.add_option("o1", &[1, 2])
+            .add_option("o2", &[1, 3])
+            .add_option("o3", &[2, 3])
+            .add_option("o4", &[3]);
+
+        assert_eq!(s.option_conflicts("o1"), vec!["o2", "o3"]);
+        assert_eq!(s.option_conflicts("o4"), vec!["o2", "o3"]);
+        assert!(s.option_conflicts("unknown").is_empty());
+    }
+
+    #[test]
+    fn options_for_item_shrinks_as_options_are_covered() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1])
+            .add_option("o2", &[1, 2])
+            .add_option("o3", &[2]);
+
+        assert_eq!(s.options_for_item(2), vec!["o2".to_string(), "o3".to_string()]);
+
+        // Selecting o1 covers item 1, which hides o2's row (it also
+        // covers item 1) from item 2's column -- item 2 stays active but
+        // loses o2 as a candidate
+        s.select("o1").unwrap();
+        assert_eq!(s.options_for_item(2), vec!["o3".to_string()]);
+    }
+
+    #[test]
+    fn export_latex_separates_mandatory_from_optional_items() {
+        let mut s: Solver = Solver::new_optional(1, 1);
+        s.add_option("o1", &[1]).add_option("o2", &[1, 2]);
+
+        let latex = s.export_latex();
+        let expected = "\\begin{tabular}{lc|c}\n \
+            & 1 & 2 \\\\\n\
+            \\hline\n\
+            o1 & 1 &  \\\\\n\
+            o2 & 1 & 1 \\\\\n\
+            \\end{tabular}\n";
+        assert_eq!(latex, expected);
+    }
+
+    #[test]
+    fn hash_agrees_with_eq_across_reordered_options() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::Hasher;
+
+        let mut a: Solver = Solver::new(3);
+        a.add_option("o1", &[1, 2])
+            .add_option("o2", &[2, 3])
+            .add_option("o3", &[3]);
+
+        let mut b: Solver = Solver::new(3);
+        b.add_option("o3", &[3])
+            .add_option("o1", &[1, 2])
+            .add_option("o2", &[2, 3]);
+
+        assert!(a == b);
+
+        let hash_of = |s: &Solver| {
+            let mut hasher = DefaultHasher::new();
+            s.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn last_error_is_none_after_a_normal_search() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1, 2]);
+
+        assert_eq!(s.next(), Some(vec!["o1".to_string()]));
+        assert_eq!(s.last_error(), None);
+    }
+
+    #[test]
+    fn solve_halts_gracefully_once_last_error_is_set() {
+        // `fail` is private and only ever reached internally if cover/uncover
+        // fails mid-search, which should never happen in practice. Calling
+        // it directly here stands in for that otherwise-untriggerable
+        // invariant violation, to check solve() honours last_error rather
+        // than panicking or continuing from an inconsistent state.
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1, 2]);
+
+        s.fail("simulated internal invariant violation");
+        assert!(matches!(s.last_error(), Some(SolverError::Internal(_))));
+        assert_eq!(s.solve(), None);
+        assert!(matches!(s.last_error(), Some(SolverError::Internal(_))));
+    }
+
+    #[test]
+    fn cover_uncover_identity() {
+        let mut s: Solver = Solver::new(4);
+        s.add_option("o1", &[1, 2])
+            .add_option("o2", &[2, 3])
+            .add_option("o3", &[3, 4])
+            .add_option("o4", &[1, 4]);
+
+        let before = s.snapshot_elements();
+        s.cover(2).unwrap();
+        assert_ne!(s.snapshot_elements(), before);
+        s.uncover(2).unwrap();
+        assert_eq!(s.snapshot_elements(), before);
+    }
+
+    #[test]
+    fn constrain_item_errors() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1]).add_option("o2", &[2]);
+
+        assert_eq!(
+            s.constrain_item(1, &["missing"]),
+            Err(SolverError::UnknownOption("missing".to_string()))
+        );
+        assert_eq!(
+            s.constrain_item(99, &["o1"]),
+            Err(SolverError::ItemOutOfRange(99))
+        );
+    }
+
+    #[test]
+    fn add_exclusion_group() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1])
+            .add_option("o2", &[2])
+            .add_option("o3", &[1])
+            .add_option("o4", &[2]);
+
+        s.add_exclusion_group(&["o1", "o3"]).unwrap();
+
+        let solutions: Vec<Vec<String>> = s.collect();
+        assert!(!solutions.is_empty());
+        for sol in &solutions {
+            let in_group = sol
+                .iter()
+                .filter(|n| ["o1", "o3"].contains(&n.as_str()))
+                .count();
+            assert!(in_group <= 1);
+        }
+    }
+
+    #[test]
+    fn forbid_pair_removes_only_the_solutions_containing_both_members() {
+        let mut before: Solver = Solver::new(2);
+        before
+            .add_option("o1", &[1])
+            .add_option("o2", &[2])
+            .add_option("o3", &[1])
+            .add_option("o4", &[2]);
+        let before_solutions: Vec<Vec<String>> = before.collect();
+        assert!(before_solutions
+            .iter()
+            .any(|sol| sol.contains(&"o1".to_string()) && sol.contains(&"o2".to_string())));
+
+        let mut after: Solver = Solver::new(2);
+        after
+            .add_option("o1", &[1])
+            .add_option("o2", &[2])
+            .add_option("o3", &[1])
+            .add_option("o4", &[2]);
+        after.forbid_pair("o1", "o2").unwrap();
+        let after_solutions: Vec<Vec<String>> = after.collect();
+
+        assert!(!after_solutions
+            .iter()
+            .any(|sol| sol.contains(&"o1".to_string()) && sol.contains(&"o2".to_string())));
+        assert!(after_solutions
+            .iter()
+            .any(|sol| sol.contains(&"o1".to_string()) && sol.contains(&"o4".to_string())));
+        assert!(after_solutions
+            .iter()
+            .any(|sol| sol.contains(&"o3".to_string()) && sol.contains(&"o2".to_string())));
+    }
+
+    #[test]
+    fn link_implied_item_covered_exactly_when_trigger_chosen() {
+        let mut s: Solver = Solver::new_optional(1, 1);
+        s.add_option("o1", &[1]).add_option("o2", &[1]);
+        s.link_implied_item(2, "o1").unwrap();
+
+        let solutions: Vec<Vec<String>> = s.collect();
+        assert_eq!(solutions.len(), 2);
+
+        let mut s: Solver = Solver::new_optional(1, 1);
+        s.add_option("o1", &[1]).add_option("o2", &[1]);
+        s.link_implied_item(2, "o1").unwrap();
+
+        s.next().unwrap();
+        assert_eq!(s.output(), vec!["o1".to_string()]);
+        assert!(s.solution_coverage().contains(&2));
+
+        s.next().unwrap();
+        assert_eq!(s.output(), vec!["o2".to_string()]);
+        assert!(!s.solution_coverage().contains(&2));
+    }
+
+    #[test]
+    fn link_implied_item_unknown_option_errors() {
+        let mut s: Solver = Solver::new_optional(1, 1);
+        s.add_option("o1", &[1]);
+
+        assert_eq!(
+            s.link_implied_item(2, "missing"),
+            Err(SolverError::UnknownOption("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn solution_slices_matches_output_indices() {
+        let mut s: Solver = Solver::new(3);
+        s.add_option("o1", &[1, 2]).add_option("o2", &[3]);
+
+        let mut expected: Solver = s.clone();
+        let mut slices = s.solution_slices();
+
+        while let Some(indices) = slices.next() {
+            let indices = indices.to_vec();
+            expected.next().unwrap();
+            assert_eq!(indices, expected.output_indices());
+        }
+        assert_eq!(expected.next(), None);
+    }
+
+    #[test]
+    fn reverse_traversal_preserves_solution_count() {
+        let mut natural: Solver = Solver::new(4);
+        natural
+            .add_option("o1", &[1, 2])
+            .add_option("o2", &[3])
+            .add_option("o3", &[2, 4])
+            .add_option("o4", &[1])
+            .add_option("o5", &[4])
+            .add_option("o6", &[3, 4]);
+
+        let mut reversed = natural.clone();
+        reversed.set_traversal(Traversal::Reverse);
+
+        let natural_solutions: Vec<Vec<String>> = natural.collect();
+        let mut reversed_solutions: Vec<Vec<String>> = reversed.collect();
+
+        assert_eq!(natural_solutions.len(), reversed_solutions.len());
+
+        let mut natural_sorted = natural_solutions;
+        natural_sorted.iter_mut().for_each(|sol| sol.sort());
+        natural_sorted.sort();
+        reversed_solutions.iter_mut().for_each(|sol| sol.sort());
+        reversed_solutions.sort();
+        assert_eq!(natural_sorted, reversed_solutions);
+    }
+
+    #[test]
+    fn first_fit_heuristic_preserves_solution_count() {
+        let mut mrv: Solver = Solver::new(4);
+        mrv.add_option("o1", &[1, 2])
+            .add_option("o2", &[3])
+            .add_option("o3", &[2, 4])
+            .add_option("o4", &[1])
+            .add_option("o5", &[4])
+            .add_option("o6", &[3, 4]);
+
+        let mut first_fit = mrv.clone();
+        first_fit.set_heuristic(Heuristic::FirstFit);
+
+        let mrv_solutions: Vec<Vec<String>> = mrv.collect();
+        let mut first_fit_solutions: Vec<Vec<String>> = first_fit.collect();
+
+        assert_eq!(mrv_solutions.len(), first_fit_solutions.len());
+
+        let mut mrv_sorted = mrv_solutions;
+        mrv_sorted.iter_mut().for_each(|sol| sol.sort());
+        mrv_sorted.sort();
+        first_fit_solutions.iter_mut().for_each(|sol| sol.sort());
+        first_fit_solutions.sort();
+        assert_eq!(mrv_sorted, first_fit_solutions);
+    }
+
+    #[test]
+    fn set_item_order_changes_branching_without_changing_the_solution_count() {
+        let mut by_mrv: Solver = Solver::new(2);
+        by_mrv
+            .add_option("x1", &[1])
+            .add_option("x2", &[1, 2])
+            .add_option("y1", &[2]);
+        assert_eq!(
+            by_mrv.next(),
+            Some(vec!["x1".to_string(), "y1".to_string()])
+        );
+
+        let mut ordered: Solver = Solver::new(2);
+        ordered
+            .add_option("x1", &[1])
+            .add_option("x2", &[1, 2])
+            .add_option("y1", &[2]);
+        ordered.set_item_order(&[2, 1]);
+        assert_eq!(ordered.next(), Some(vec!["x2".to_string()]));
+
+        // Same set of solutions either way, just discovered in a different
+        // order
+        let mut default_count: Solver = Solver::new(2);
+        default_count
+            .add_option("x1", &[1])
+            .add_option("x2", &[1, 2])
+            .add_option("y1", &[2]);
+        let mut ordered_count: Solver = Solver::new(2);
+        ordered_count
+            .add_option("x1", &[1])
+            .add_option("x2", &[1, 2])
+            .add_option("y1", &[2]);
+        ordered_count.set_item_order(&[2, 1]);
+        assert_eq!(default_count.count(), ordered_count.count());
+    }
+
+    #[test]
+    fn add_option_checked_fails_cleanly_at_node_limit() {
+        let mut s: Solver = Solver::new(3);
+        // Just enough room for a single-item option (+2 elements) but not
+        // a two-item one (+3 elements) on top of the existing elements
+        let limit = s.elements.len() + 2;
+        s.set_node_limit(limit);
+
+        assert!(s.add_option_checked("o1", &[1]).is_ok());
+        let elements_after_o1 = s.elements.len();
+
+        let result = s.add_option_checked("o2", &[2, 3]);
+        assert!(matches!(result, Err(SolverError::NodeLimitExceeded(max)) if max == limit));
+        // The rejected option must not have partially grown the solver
+        assert_eq!(s.elements.len(), elements_after_o1);
+    }
+
+    #[test]
+    fn from_reader_with_progress_streams_options_and_reports_progress() {
+        use std::io::Cursor;
+
+        let input = "4 1 14\no1 1 3\no2 2 4\no3 1 5\no4 3\no5 3 5\n";
+        let mut progress_calls = Vec::new();
+        let mut s: Solver = Solver::from_reader_with_progress(Cursor::new(input), 2, |n| {
+            progress_calls.push(n)
+        })
+        .unwrap();
+
+        assert_eq!(progress_calls, vec![2, 4]);
+        assert_eq!(s.next(), Some(vec!["o2".to_string(), "o1".to_string()]));
+        assert_eq!(s.next(), Some(vec!["o2".to_string(), "o3".to_string(), "o4".to_string()]));
+        assert_eq!(s.next(), None);
+    }
+
+    #[test]
+    fn from_reader_with_progress_rejects_a_malformed_header() {
+        use std::io::Cursor;
+
+        let result: Result<Solver, SolverError> =
+            Solver::from_reader_with_progress(Cursor::new("not a header\n"), 1, |_| {});
+        assert!(matches!(result, Err(SolverError::MalformedInput(_))));
+    }
+
+    #[test]
+    fn collect_reversed_is_forward_collect_reversed() {
+        let mut s: Solver = Solver::new(3);
+        s.add_option("o1", &[1])
+            .add_option("o2", &[1])
+            .add_option("o3", &[2, 3]);
+
+        let mut forward: Solver = s.clone();
+        let forward_solutions: Vec<Vec<String>> = forward.by_ref().collect();
+        let mut expected = forward_solutions;
+        expected.reverse();
+
+        assert_eq!(s.collect_reversed(), expected);
+    }
+
+    #[test]
+    fn select_after_iteration_started_errors() {
+        let mut s: Solver = Solver::new(3);
+        s.add_option("o1", &[1])
+            .add_option("o2", &[1])
+            .add_option("o3", &[2, 3]);
+
+        s.next();
+        assert_eq!(s.select("o1"), Err(SolverError::AlreadyIterating));
+    }
+
+    #[test]
+    fn exhausted_solver_keeps_returning_none() {
+        let mut s: Solver = Solver::new(1);
+        s.add_option("o1", &[1]);
+
+        assert!(s.next().is_some());
+        for _ in 0..5 {
+            assert_eq!(s.next(), None);
+        }
+    }
+
+    #[test]
+    fn eq_ignores_option_insertion_order_and_search_progress() {
+        let mut a: Solver = Solver::new_optional(3, 1);
+        a.add_option("o1", &[1, 3])
+            .add_option("o2", &[2, 4])
+            .add_option("o3", &[1, 4]);
+
+        let mut b: Solver = Solver::new_optional(3, 1);
+        b.add_option("o3", &[1, 4])
+            .add_option("o1", &[1, 3])
+            .add_option("o2", &[2, 4]);
+
+        assert!(a == b);
+
+        // Advancing one solver's search doesn't change its problem
+        // definition
+        a.next();
+        assert!(a == b);
+
+        // A different item count or option item-set makes them unequal
+        let mut c: Solver = Solver::new_optional(3, 1);
+        c.add_option("o1", &[1, 3])
+            .add_option("o2", &[2, 4])
+            .add_option("o3", &[1, 3]);
+        assert!(a != c);
+    }
+
+    #[test]
+    fn feasible_options_excludes_an_option_that_strands_another_item() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("only", &[1, 2]).add_option("conflicting", &[1]);
+
+        // "conflicting" covers item 1 but leaves item 2 uncoverable by
+        // anything else, so it can never appear in a full solution
+        assert_eq!(s.feasible_options(), vec!["only".to_string()]);
+        assert!(!s.probe_option("conflicting"));
+        assert!(s.is_satisfiable());
+    }
+
+    #[test]
+    fn estimated_difficulty_classifies_by_average_item_degree() {
+        // Every item covered by a single option: fully forced, trivially solvable
+        let mut trivial: Solver = Solver::new(2);
+        trivial.add_option("o1", &[1]).add_option("o2", &[2]);
+        assert_eq!(trivial.estimated_difficulty(), DifficultyClass::Trivial);
+
+        // Every item covered by 9 options and none narrowed down: dense
+        // and wide open, like a blank Sudoku
+        let mut intractable: Solver = Solver::new(2);
+        for i in 0..9 {
+            intractable.add_option(&format!("a{i}"), &[1]);
+            intractable.add_option(&format!("b{i}"), &[2]);
+        }
+        assert_eq!(intractable.estimated_difficulty(), DifficultyClass::LikelyIntractable);
+    }
+
+    #[test]
+    fn lexicographic_first_solution_returns_the_smallest_sorted_index_set() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1])
+            .add_option("o2", &[2])
+            .add_option("o3", &[1, 2])
+            .add_option("o4", &[2]);
+
+        // Solutions by sorted index set: {0,1}, {0,3}, {2} -- {0,1} is smallest
+        assert_eq!(s.lexicographic_first_solution(), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn prune_unreachable_options_leaves_solutions_and_counts_unchanged() {
+        let mut pruned: Solver = Solver::new(2);
+        pruned.add_option("only", &[1, 2]).add_option("conflicting", &[1]);
+        // "conflicting" covers item 1 but leaves item 2 uncoverable by
+        // anything else, so it can never appear in a full solution
+        assert_eq!(pruned.prune_unreachable_options(), Ok(1));
+
+        let mut unpruned: Solver = Solver::new(2);
+        unpruned.add_option("only", &[1, 2]).add_option("conflicting", &[1]);
+
+        let mut pruned_solutions: Vec<Vec<String>> = pruned.by_ref().collect();
+        let mut unpruned_solutions: Vec<Vec<String>> = unpruned.by_ref().collect();
+        pruned_solutions.sort();
+        unpruned_solutions.sort();
+        assert_eq!(pruned_solutions, unpruned_solutions);
+    }
+
+    #[test]
+    fn diff_problems_reports_an_option_present_on_only_one_side() {
+        let mut a: Solver = Solver::new(3);
+        a.add_option("o1", &[1, 2]).add_option("o2", &[3]);
+
+        let mut b: Solver = Solver::new(3);
+        b.add_option("o1", &[1, 2]).add_option("o2", &[2, 3]);
+
+        assert_eq!(
+            a.diff_problems(&b),
+            vec![
+                ProblemDiff::OnlyInThis {
+                    name: "o2".to_string(),
+                    items: vec![3]
+                },
+                ProblemDiff::OnlyInOther {
+                    name: "o2".to_string(),
+                    items: vec![2, 3]
+                },
+            ]
+        );
+        assert!(a.diff_problems(&a.clone()).is_empty());
+    }
+
+    #[test]
+    fn add_option_colored_lets_options_share_a_same_colored_item() {
+        let mut s: Solver = Solver::new_optional(3, 1);
+        // Item 4 is optional and colored. Items 1 and 2 each have only one
+        // covering option, so o1 and o2 are always both selected and
+        // always commit item 4 to color 7 between them; o3's color 9 for
+        // the same item can therefore never survive, leaving o4 (which
+        // doesn't touch item 4 at all) as the only way to cover item 3
+        s.add_option_colored("o1", &[1], &[(4, 7)])
+            .add_option_colored("o2", &[2], &[(4, 7)])
+            .add_option_colored("o3", &[3], &[(4, 9)])
+            .add_option("o4", &[3]);
+
+        let solutions: Vec<Vec<String>> = s.by_ref().collect();
+        assert_eq!(
+            solutions,
+            vec![vec!["o1".to_string(), "o2".to_string(), "o4".to_string()]]
+        );
+    }
+
+    #[test]
+    fn merge_preserves_colors_so_merged_options_can_still_share_an_item() {
+        let mut base: Solver = Solver::new_optional(2, 1);
+        base.add_option_colored("o1", &[1], &[(3, 7)]);
+
+        let mut right: Solver = Solver::new_optional(2, 1);
+        right.add_option_colored("o2", &[2], &[(3, 7)]);
+
+        base.merge(&right, 0).unwrap();
+
+        assert_eq!(
+            base.next(),
+            Some(vec!["o1".to_string(), "o2".to_string()])
+        );
+    }
+
+    #[test]
+    fn validate_solution_accepts_a_shared_color_merge_produced() {
+        let mut base: Solver = Solver::new_optional(2, 1);
+        base.add_option_colored("o1", &[1], &[(3, 7)]);
+
+        let mut right: Solver = Solver::new_optional(2, 1);
+        right.add_option_colored("o2", &[2], &[(3, 7)]);
+
+        base.merge(&right, 0).unwrap();
+
+        assert_eq!(base.validate_solution(&["o1", "o2"]), Ok(()));
+    }
+
+    #[test]
+    fn count_solutions_matches_iterator_count_without_building_names() {
+        let build = || {
+            let mut s: Solver = Solver::new(3);
+            s.add_option("o1", &[1, 2])
+                .add_option("o2", &[3])
+                .add_option("o3", &[1])
+                .add_option("o4", &[2, 3]);
+            s
+        };
+
+        let mut counted = build();
+        let via_iterator = build().count();
+
+        assert_eq!(counted.count_solutions(), via_iterator);
+        assert_eq!(via_iterator, 2);
+    }
+
+    #[test]
+    fn backbone_options_finds_the_option_every_solution_depends_on() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("only", &[1, 2]).add_option("extra", &[1]);
+
+        // Without "only", item 2 can never be covered, so the problem
+        // becomes unsatisfiable -- "only" is load-bearing. "extra" isn't:
+        // removing it leaves "only" free to solve the problem alone
+        assert_eq!(s.backbone_options(), vec!["only".to_string()]);
+    }
+
+    #[test]
+    fn count_up_to_parallel_stops_at_the_limit_on_a_problem_with_many_solutions() {
+        // 5 independent "slots", each with 4 non-conflicting choices: every
+        // combination is a solution, so there are 4^5 = 1024 of them, far
+        // more than any limit used below
+        let mut s: Solver = Solver::new(5);
+        for slot in 1..=5 {
+            for choice in 1..=4 {
+                s.add_option(&format!("slot{slot}-{choice}"), &[slot]);
+            }
+        }
+
+        assert_eq!(s.count_up_to_parallel(10), 10);
+        assert_eq!(s.count_up_to_parallel(0), 0);
+        assert_eq!(s.clone().count(), 1024);
+    }
+
+    #[test]
+    fn seed_from_solution_resumes_at_the_following_solution() {
+        let build = || {
+            let mut s: Solver = Solver::new(3);
+            s.add_option("a1", &[1, 2])
+                .add_option("a2", &[1])
+                .add_option("b1", &[2])
+                .add_option("b2", &[2, 3])
+                .add_option("c", &[3]);
+            s
+        };
+
+        let all: Vec<Vec<String>> = build().collect();
+        assert!(all.len() > 2, "need several solutions for this test to mean anything");
+
+        for (i, sol) in all.iter().enumerate() {
+            let refs: Vec<&str> = sol.iter().map(String::as_str).collect();
+            let mut resumed = build();
+            resumed.seed_from_solution(&refs).unwrap();
+            assert_eq!(resumed.collect::<Vec<_>>(), all[i + 1..]);
+        }
+    }
+
+    #[test]
+    fn seed_from_solution_accepts_a_solution_sharing_a_colored_item() {
+        let mut s: Solver = Solver::new_optional(2, 1);
+        s.add_option_colored("o1", &[1], &[(3, 7)])
+            .add_option_colored("o2", &[2], &[(3, 7)])
+            .add_option_colored("o3", &[2], &[(3, 9)]);
+
+        assert_eq!(s.validate_solution(&["o1", "o2"]), Ok(()));
+        s.seed_from_solution(&["o1", "o2"]).unwrap();
+        assert_eq!(s.next(), None);
+    }
+
+    #[test]
+    fn resume_accepts_a_cursor_sharing_a_colored_item() {
+        let build = || {
+            let mut s: Solver = Solver::new_optional(2, 1);
+            s.add_option_colored("o1", &[1], &[(3, 7)])
+                .add_option_colored("o2", &[2], &[(3, 7)])
+                .add_option_colored("o3", &[2], &[(3, 9)]);
+            s
+        };
+
+        let mut s = build();
+        s.next();
+        let cursor = s.checkpoint();
+
+        let mut resumed = build();
+        resumed.resume(cursor).unwrap();
+        assert_eq!(resumed.next(), None);
+    }
+
+    #[test]
+    fn seed_from_solution_rejects_an_invalid_solution() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1]).add_option("o2", &[2]);
+
+        assert_eq!(
+            s.seed_from_solution(&["o1"]),
+            Err(SolverError::ItemUncovered(2))
+        );
+        assert_eq!(
+            s.seed_from_solution(&["nope"]),
+            Err(SolverError::UnknownOption("nope".to_string()))
+        );
+    }
+
+    #[test]
+    fn checkpoint_and_resume_continue_a_paused_enumeration() {
+        let build = || {
+            let mut s: Solver = Solver::new(3);
+            s.add_option("a1", &[1, 2])
+                .add_option("a2", &[1])
+                .add_option("b1", &[2])
+                .add_option("b2", &[2, 3])
+                .add_option("c", &[3]);
+            s
+        };
+
+        let all: Vec<Vec<String>> = build().collect();
+        assert!(
+            all.len() > 2,
+            "need several solutions for this test to mean anything"
+        );
+
+        // Pause mid-enumeration, after the first solution but with more
+        // still to come, and checkpoint from there
+        for i in 0..all.len() {
+            let mut s = build();
+            for _ in 0..=i {
+                s.next();
+            }
+            let cursor = s.checkpoint();
+            assert_eq!(cursor.l, all[i].len());
+
+            let mut resumed = build();
+            resumed.resume(cursor).unwrap();
+            assert_eq!(resumed.collect::<Vec<_>>(), all[i + 1..]);
+        }
+    }
+
+    #[test]
+    fn resume_rejects_a_cursor_naming_an_unknown_option() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1]).add_option("o2", &[2]);
+
+        let cursor = SearchCursor {
+            l: 1,
+            committed: vec!["nope".to_string()],
+        };
+        assert_eq!(
+            s.resume(cursor),
+            Err(SolverError::UnknownOption("nope".to_string()))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn checkpoint_json_round_trips_through_resume_from_json() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("a1", &[1])
+            .add_option("a2", &[1])
+            .add_option("b", &[2]);
+        s.next();
+
+        let json = s.checkpoint_json();
+
+        let mut resumed: Solver = Solver::new(2);
+        resumed
+            .add_option("a1", &[1])
+            .add_option("a2", &[1])
+            .add_option("b", &[2]);
+        resumed.resume_from_json(&json).unwrap();
+
+        assert_eq!(resumed.collect::<Vec<_>>(), s.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn into_iter_owned_names_matches_the_plain_iterator_and_shares_storage() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("a1", &[1])
+            .add_option("a2", &[1])
+            .add_option("b", &[2]);
+
+        let expected: Vec<Vec<String>> = s.clone().collect();
+        assert!(
+            expected.iter().filter(|sol| sol.contains(&"b".to_string())).count() > 1,
+            "need \"b\" to recur across solutions for this test to mean anything"
+        );
+
+        let solutions: Vec<Vec<Arc<str>>> = s.into_iter_owned_names().collect();
+        assert_eq!(
+            solutions,
+            expected
+                .iter()
+                .map(|sol| sol.iter().map(|n| Arc::from(n.as_str())).collect())
+                .collect::<Vec<Vec<Arc<str>>>>()
+        );
+
+        // Every occurrence of "b" across solutions shares the same
+        // backing allocation rather than being freshly cloned
+        assert!(Arc::ptr_eq(&solutions[0][0], &solutions[1][0]));
+    }
+
+    #[test]
+    fn solve_observed_reports_every_solution_and_backtrack() {
+        #[derive(Default)]
+        struct Recorder {
+            solutions: Vec<usize>,
+            backtracks: usize,
+        }
+        impl Observer for Recorder {
+            fn on_solution(&mut self, depth: usize) {
+                self.solutions.push(depth);
+            }
+            fn on_backtrack(&mut self) {
+                self.backtracks += 1;
+            }
+        }
+
+        let mut s: Solver = Solver::new(2);
+        s.add_option("a1", &[1])
+            .add_option("a2", &[1])
+            .add_option("b", &[2]);
+
+        let mut plain: Solver = s.clone();
+        let expected: Vec<Vec<String>> = plain.by_ref().collect();
+
+        let mut recorder = Recorder::default();
+        let mut found = Vec::new();
+        while let Some(sol) = s.solve_observed(&mut recorder) {
+            found.push(sol);
+        }
+
+        assert_eq!(found, expected);
+        assert_eq!(recorder.solutions, vec![2; expected.len()]);
+        assert!(recorder.backtracks > 0);
+    }
+
+    #[test]
+    fn with_symmetry_pruner_collapses_mirrored_solutions() {
+        // Item 1 is covered by either "1a" or "1b", item 2 by either "2a"
+        // or "2b", independently -- four solutions in all, forming two
+        // mirror-image pairs under swapping a<->b on both items at once
+        fn mirror(i: usize) -> usize {
+            match i {
+                0 => 1,
+                1 => 0,
+                2 => 3,
+                3 => 2,
+                _ => i,
+            }
+        }
+
+        let mut s: Solver = Solver::new(2);
+        s.add_option("1a", &[1])
+            .add_option("1b", &[1])
+            .add_option("2a", &[2])
+            .add_option("2b", &[2]);
+        assert_eq!(s.clone().count(), 4);
+
+        s.with_symmetry_pruner(|picks| {
+            let orig = picks[0] * 10 + picks[1];
+            let mirrored = mirror(picks[0]) * 10 + mirror(picks[1]);
+            orig.min(mirrored) as u64
+        });
+        assert_eq!(s.count(), 2);
+    }
+
+    #[test]
+    fn events_trace_covers_descends_and_backtracking() {
+        // Two options both cover item 1: the item is covered once and both
+        // are tried as its candidate before it's finally uncovered once the
+        // last candidate is exhausted
+        let mut s: Solver = Solver::new(1);
+        s.add_option("o1", &[1]).add_option("o2", &[1]);
+
+        let events: Vec<SearchEvent> = s.events().collect();
+        assert_eq!(
+            events,
+            vec![
+                SearchEvent::Cover(1),
+                SearchEvent::Descend,
+                SearchEvent::Solution(vec!["o1".to_string()]),
+                SearchEvent::Ascend,
+                SearchEvent::Descend,
+                SearchEvent::Solution(vec!["o2".to_string()]),
+                SearchEvent::Ascend,
+                SearchEvent::Uncover(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn output_with_formats_sudoku_style_names() {
+        let mut s: Solver = Solver::new(1);
+        s.add_option("R5C3#7", &[1]);
+        s.next();
+
+        assert_eq!(
+            s.output_with(|name| name.replace('#', "=")),
+            vec!["R5C3=7".to_string()]
+        );
+    }
+
+    #[test]
+    fn cover_scoped_restores_structure_when_dropped() {
+        let mut s: Solver = Solver::new(4);
+        s.add_option("o1", &[1, 2])
+            .add_option("o2", &[2, 3])
+            .add_option("o3", &[3, 4])
+            .add_option("o4", &[1, 4]);
+
+        let before = s.snapshot_elements();
+        {
+            let guard = s.cover_scoped(2).unwrap();
+            assert_ne!(guard.snapshot_elements(), before);
+        }
+        assert_eq!(s.snapshot_elements(), before);
+    }
+
+    #[test]
+    fn cover_scoped_rejects_out_of_range_item() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1]);
+        assert_eq!(
+            s.cover_scoped(99).err(),
+            Some(SolverError::ItemOutOfRange(99))
+        );
+    }
+
+    #[test]
+    fn min_set_cover_finds_the_known_minimum() {
+        // The classic textbook example: {1,2,3}, {2,4,5}, {3,4}, {4,5,6}
+        // over a universe of 1..=6, whose minimum cover is two sets.
+        let mut s: Solver = Solver::new(6);
+        s.add_option("a", &[1, 2, 3])
+            .add_option("b", &[2, 4, 5])
+            .add_option("c", &[3, 4])
+            .add_option("d", &[4, 5, 6]);
+
+        let mut cover = s.min_set_cover().unwrap();
+        cover.sort();
+        assert_eq!(cover, vec!["a".to_string(), "d".to_string()]);
+    }
+
+    #[test]
+    fn min_set_cover_returns_none_when_an_item_is_uncoverable() {
+        let mut s: Solver = Solver::new(3);
+        s.add_option("o1", &[1, 2]);
+        assert_eq!(s.min_set_cover(), None);
+    }
+
+    #[test]
+    fn has_unique_solution_distinguishes_zero_one_and_many() {
+        let mut unique: Solver = Solver::new(2);
+        unique.add_option("o1", &[1]).add_option("o2", &[2]);
+        assert!(unique.has_unique_solution());
+
+        let mut multiple: Solver = Solver::new(1);
+        multiple.add_option("o1", &[1]).add_option("o2", &[1]);
+        assert!(!multiple.has_unique_solution());
+
+        let mut none: Solver = Solver::new(2);
+        none.add_option("o1", &[1]);
+        assert!(!none.has_unique_solution());
+    }
+
+    #[test]
+    fn perfect_matching_counts_a_four_cycles_matchings() {
+        // A 4-cycle 1-2-3-4-1 has exactly 2 perfect matchings: the two
+        // "opposite" pairs of edges, {1-2, 3-4} and {2-3, 4-1}
+        let edges = [(1, 2), (2, 3), (3, 4), (4, 1)];
+        let mut s = Solver::perfect_matching(&edges, 4);
+
+        let mut matchings: Vec<Vec<(usize, usize)>> = Vec::new();
+        while s.next().is_some() {
+            let mut matching: Vec<(usize, usize)> =
+                s.output_meta().into_iter().map(|m| *m.unwrap()).collect();
+            matching.sort_unstable();
+            matchings.push(matching);
+        }
+        assert_eq!(matchings.len(), 2);
+        assert!(matchings.contains(&vec![(1, 2), (3, 4)]));
+        assert!(matchings.contains(&vec![(2, 3), (4, 1)]));
+    }
+
+    #[test]
+    fn solutions_batched_concatenates_back_to_the_flat_solution_list() {
+        let mut flat: Solver = Solver::new(3);
+        flat.add_option("o1", &[1])
+            .add_option("o2", &[2])
+            .add_option("o3", &[3])
+            .add_option("o4", &[1, 2])
+            .add_option("o5", &[3]);
+        let expected: Vec<Vec<String>> = flat.by_ref().collect();
+
+        let mut batched: Solver = Solver::new(3);
+        batched
+            .add_option("o1", &[1])
+            .add_option("o2", &[2])
+            .add_option("o3", &[3])
+            .add_option("o4", &[1, 2])
+            .add_option("o5", &[3]);
+        let batches: Vec<Vec<Vec<String>>> = batched.solutions_batched(2).collect();
+
+        assert!(batches.iter().all(|batch| batch.len() <= 2));
+        let flattened: Vec<Vec<String>> = batches.into_iter().flatten().collect();
+        assert_eq!(flattened, expected);
+    }
+
+    #[test]
+    fn an_uncoverable_optional_item_is_never_branched_on() {
+        // 2 mandatory items, 1 optional item that no option covers at all
+        // (l == 0): branching on it would be a dead end, since cover()
+        // would immediately fail to find any option to try
+        let mut s: Solver = Solver::new_optional(2, 1);
+        s.add_option("o1", &[1]).add_option("o2", &[2]);
+
+        // If the optional item were ever selected for branching, this
+        // solve would spuriously fail to find the one valid completion
+        assert_eq!(s.next(), Some(vec!["o1".to_string(), "o2".to_string()]));
+        assert_eq!(s.next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "num_optional exceeds num_items")]
+    fn from_description_rejects_more_optional_items_than_total_items() {
+        let description = ProblemDescription {
+            num_items: 2,
+            num_optional: 3,
+            options: vec![],
+        };
+        let _: Solver = Solver::from_description(&description);
+    }
+
+    #[test]
+    fn solution_deltas_reconstruct_full_solutions() {
+        let mut expected: Solver = Solver::new(2);
+        expected
+            .add_option("o1", &[1])
+            .add_option("o2", &[2])
+            .add_option("o3", &[1, 2])
+            .add_option("o4", &[2]);
+        let expected: Vec<Vec<usize>> = expected
+            .solutions_with_indices()
+            .map(|(_names, indices)| indices)
+            .collect();
+
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1])
+            .add_option("o2", &[2])
+            .add_option("o3", &[1, 2])
+            .add_option("o4", &[2]);
+
+        let mut current: HashSet<usize> = HashSet::new();
+        let mut reconstructed: Vec<Vec<usize>> = vec![];
+        for (added, removed) in s.solution_deltas() {
+            for index in removed {
+                current.remove(&index);
+            }
+            for index in added {
+                current.insert(index);
+            }
+            let mut solution: Vec<usize> = current.iter().copied().collect();
+            solution.sort_unstable();
+            reconstructed.push(solution);
         }
+
+        let mut expected_sorted: Vec<Vec<usize>> = expected.into_iter().map(|mut sol| {
+            sol.sort_unstable();
+            sol
+        }).collect();
+        expected_sorted.sort();
+        let mut reconstructed_sorted = reconstructed;
+        reconstructed_sorted.sort();
+        assert_eq!(reconstructed_sorted, expected_sorted);
+    }
+
+    #[test]
+    fn try_fold_solutions_aborts_once_the_threshold_is_reached() {
+        let mut s: Solver = Solver::new(1);
+        s.add_option("o1", &[1]).add_option("o2", &[1]).add_option("o3", &[1]);
+
+        let result: Result<usize, usize> = s.try_fold_solutions(0, |count, _sol| {
+            let count = count + 1;
+            if count >= 2 {
+                Err(count)
+            } else {
+                Ok(count)
+            }
+        });
+
+        assert_eq!(result, Err(2));
+        // The third solution was never visited: one more remains
+        assert_eq!(s.count(), 1);
+    }
+
+    #[test]
+    fn try_fold_solutions_runs_to_completion_when_f_never_errs() {
+        let mut s: Solver = Solver::new(1);
+        s.add_option("o1", &[1]).add_option("o2", &[1]);
+
+        let result: Result<usize, ()> =
+            s.try_fold_solutions(0, |count, _sol| Ok(count + 1));
+
+        assert_eq!(result, Ok(2));
+    }
+
+    #[test]
+    fn root_branching_factor_matches_the_known_root_item_degree() {
+        let mut s: Solver = Solver::new(2);
+        s.add_option("o1", &[1])
+            .add_option("o2", &[1])
+            .add_option("o3", &[1, 2])
+            .add_option("o4", &[2]);
+        assert_eq!(s.root_branching_factor(), 2);
+    }
+
+    #[test]
+    fn root_branching_factor_is_zero_with_no_mandatory_items() {
+        let s: Solver = Solver::new(0);
+        assert_eq!(s.root_branching_factor(), 0);
     }
 }
@@ -1,4 +1,87 @@
 use crate::solver::Solver;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Errors returned by [Sudoku::new_from_input] when the supplied clues are
+/// malformed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SudokuError {
+    /// The input length is not a perfect fourth power (`n**4` for some `n`)
+    InvalidLength(usize),
+    /// A cell holds a value outside the valid `0..=n*n` range (`0` meaning blank)
+    InvalidValue { row: usize, col: usize, value: usize },
+    /// Two givens conflict: the same value appears twice in a row, column, or box
+    DuplicateGiven {
+        row: usize,
+        col: usize,
+        value: usize,
+    },
+    /// [parse_bordered](Sudoku::parse_bordered) was given text that isn't
+    /// shaped like a bordered grid (wrong number of lines, or a line of
+    /// the wrong length)
+    MalformedGrid(String),
+    /// [parse_bordered](Sudoku::parse_bordered)'s borders don't split the
+    /// grid into exactly `n*n` regions
+    InvalidRegionCount { found: usize, expected: usize },
+    /// A region detected by [parse_bordered](Sudoku::parse_bordered)
+    /// doesn't contain exactly `n*n` cells
+    InvalidRegionSize {
+        region: usize,
+        found: usize,
+        expected: usize,
+    },
+    /// The underlying [Solver] rejected a constraint raised while building
+    /// the puzzle (e.g. [constrain_item](crate::solver::Solver::constrain_item)
+    /// on an out-of-range cell). The wrapped [SolverError] is exposed via
+    /// [source](std::error::Error::source) for callers that want the full
+    /// causal chain
+    ConstructionFailed(crate::solver::SolverError),
+    /// [solve_unique](Sudoku::solve_unique) found no valid completion at all
+    NoSolution,
+    /// [solve_unique](Sudoku::solve_unique) found more than one valid
+    /// completion, so there's no single answer to return
+    MultipleSolutions,
+}
+
+impl fmt::Display for SudokuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SudokuError::InvalidLength(len) => {
+                write!(f, "input length {len} is not a perfect fourth power")
+            }
+            SudokuError::InvalidValue { row, col, value } => {
+                write!(f, "R{row}C{col} has invalid value {value}")
+            }
+            SudokuError::DuplicateGiven { row, col, value } => {
+                write!(f, "R{row}C{col}#{value} conflicts with another given")
+            }
+            SudokuError::MalformedGrid(reason) => write!(f, "malformed bordered grid: {reason}"),
+            SudokuError::InvalidRegionCount { found, expected } => {
+                write!(f, "found {found} regions, expected {expected}")
+            }
+            SudokuError::InvalidRegionSize {
+                region,
+                found,
+                expected,
+            } => write!(f, "region {region} has {found} cells, expected {expected}"),
+            SudokuError::ConstructionFailed(err) => {
+                write!(f, "sudoku construction failed: {err}")
+            }
+            SudokuError::NoSolution => write!(f, "puzzle has no solution"),
+            SudokuError::MultipleSolutions => write!(f, "puzzle has more than one solution"),
+        }
+    }
+}
+
+impl std::error::Error for SudokuError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SudokuError::ConstructionFailed(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 /// Implements sudoku solver
 ///
@@ -18,7 +101,7 @@ use crate::solver::Solver;
 /// ];
 ///
 /// // Create new sudoku from this grid
-/// let mut s = Sudoku::new_from_input(&sudoku);
+/// let mut s = Sudoku::new_from_input(&sudoku).unwrap();
 ///
 /// let true_solution = vec![
 ///     5, 3, 4, 6, 7, 8, 9, 1, 2,
@@ -39,7 +122,16 @@ use crate::solver::Solver;
 pub struct Sudoku {
     pub solver: Solver,
     input: Vec<usize>,
-    n: usize,
+    /// Grid edge length: `n*n` for [new](Sudoku::new)/[new_with_regions](Sudoku::new_with_regions),
+    /// or `box_rows*box_cols` for [new_rect](Sudoku::new_rect)
+    side: usize,
+    /// 0-indexed region id of each row-major cell, as taken by
+    /// [new_with_regions](Sudoku::new_with_regions) (standard `n`x`n`
+    /// sub-boxes for [new](Sudoku::new)); kept around for
+    /// [peer_cells](Sudoku::peer_cells), which is purely geometric and
+    /// shouldn't depend on how much of the solver's constraint structure
+    /// clues have already covered
+    regions: Vec<usize>,
 }
 
 impl Sudoku {
@@ -52,7 +144,7 @@ impl Sudoku {
         // 3. Each col must have a 1, each col must have a 2, ...n^2
         // 4. Each sub-square must have a 1, each sub-square must have a 2, ...n^2
         #[allow(non_snake_case)]
-        let N = n * n; // Sudoku edge length
+        let N = n.checked_mul(n).expect("sudoku board too large: n*n overflows usize"); // Sudoku edge length
 
         //1: N*N constraints
         //2: N rows * N numbers
@@ -60,7 +152,22 @@ impl Sudoku {
         //4: N cols * N numbers
         //T: 4 N**2 items
 
-        let mut solver = Solver::new(4 * N * N);
+        // Checked so a huge n fails loudly here rather than silently
+        // wrapping into a mis-sized solver
+        let item_count = N
+            .checked_mul(N)
+            .and_then(|nn| nn.checked_mul(4))
+            .expect("sudoku board too large: 4*n^4 overflows usize");
+
+        let mut solver: Solver = Solver::new(item_count);
+
+        let regions: Vec<usize> = (0..N * N)
+            .map(|i| {
+                let row0 = i / N;
+                let col0 = i % N;
+                col0 / n + n * (row0 / n)
+            })
+            .collect();
 
         // And how many options are there?
         // Each cell may contain N options, and there are N*N, so N*N*N options
@@ -112,15 +219,163 @@ impl Sudoku {
 
         Sudoku {
             solver,
-            n,
+            side: N,
             input: vec![],
+            regions,
         }
     }
 
+    /// Like [new](Sudoku::new), but the sub-region each cell belongs to is
+    /// given explicitly by `regions[i]`, the 0-indexed region id of
+    /// row-major cell `i`, rather than computed from the standard `n`x`n`
+    /// sub-boxes -- this is what lets a jigsaw Sudoku (irregularly shaped
+    /// regions instead of square boxes) be built, and is what
+    /// [parse_bordered](Sudoku::parse_bordered) constructs from a parsed
+    /// grid
+    ///
+    /// `regions` must have `n*n*n*n` entries and partition them into
+    /// exactly `n*n` groups of `n*n` cells each; this isn't validated here
+    /// (use [parse_bordered](Sudoku::parse_bordered) for a checked entry
+    /// point). Region ids below `n*n` grouped into unequal sizes produce a
+    /// solver with a broken constraint that silently has no solutions,
+    /// while a `regions` with fewer than `n*n*n*n` entries panics on
+    /// indexing
+    pub fn new_with_regions(n: usize, regions: &[usize]) -> Sudoku {
+        #[allow(non_snake_case)]
+        let N = n.checked_mul(n).expect("sudoku board too large: n*n overflows usize");
+
+        Self::build_from_regions(N, regions)
+    }
+
+    /// Shared constraint-matrix builder behind [new_with_regions](Sudoku::new_with_regions)
+    /// and [new_rect](Sudoku::new_rect): `side` is the grid's actual edge
+    /// length (not the square-box "n" of [new](Sudoku::new)), and `regions`
+    /// gives the 0-indexed region id of each row-major cell
+    fn build_from_regions(side: usize, regions: &[usize]) -> Sudoku {
+        let item_count = side
+            .checked_mul(side)
+            .and_then(|nn| nn.checked_mul(4))
+            .expect("sudoku board too large: 4*side^2 overflows usize");
+
+        let mut solver: Solver = Solver::new(item_count);
+
+        for row in 1..=side {
+            for col in 1..=side {
+                for val in 1..=side {
+                    let constraint_name = format!("R{}C{}#{}", row, col, val);
+                    let cell_con = col + (row - 1) * side;
+                    let row_con = side * side + side * (row - 1) + val;
+                    let col_con = 2 * side * side + side * (col - 1) + val;
+                    let sub = regions[(row - 1) * side + (col - 1)];
+                    let sub_con = 3 * side * side + side * sub + val;
+                    solver.add_option(&constraint_name, &[cell_con, row_con, col_con, sub_con]);
+                }
+            }
+        }
+
+        Sudoku {
+            solver,
+            side,
+            input: vec![],
+            regions: regions.to_vec(),
+        }
+    }
+
+    /// Like [new](Sudoku::new), but for rectangular (non-square) boxes, e.g.
+    /// a 6x6 grid made up of 2x3 boxes rather than a square `n`x`n` grid made
+    /// up of `n`x`n` boxes
+    ///
+    /// The grid side is `box_rows * box_cols`, and the box each cell belongs
+    /// to generalises the square-box formula `(col-1)/n + n*((row-1)/n)` to
+    /// `(col-1)/box_cols + box_rows*((row-1)/box_rows)`
+    ///
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// // A 6x6 grid is 2x3 boxes, not 3x3 or any other square shape
+    /// let s = Sudoku::new_rect(2, 3);
+    /// assert_eq!(s.to_matrix().0.len(), 6 * 6 * 6);
+    /// // 5 row peers + 5 column peers + 2 more from the 2x3 box, (2,2) and (2,3)
+    /// assert_eq!(s.peer_cells(1, 1).len(), 12);
+    /// ```
+    pub fn new_rect(box_rows: usize, box_cols: usize) -> Sudoku {
+        let side = box_rows
+            .checked_mul(box_cols)
+            .expect("sudoku board too large: box_rows*box_cols overflows usize");
+
+        let regions: Vec<usize> = (0..side * side)
+            .map(|i| {
+                let row0 = i / side;
+                let col0 = i % side;
+                col0 / box_cols + box_rows * (row0 / box_rows)
+            })
+            .collect();
+
+        Self::build_from_regions(side, &regions)
+    }
+
     /// Initialises an appropriately sized Sudoku with all of the correct
     /// constraints, and then selects all of the options corresponding the the
     /// non-zero entires in `input`
-    pub fn new_from_input(input: &[usize]) -> Self {
+    ///
+    /// Validates that `input` has a valid length, that every value falls in
+    /// range, and that no two givens already conflict, returning a
+    /// [SudokuError] describing the first problem found rather than
+    /// panicking or silently mis-solving. For the old panicking fast path
+    /// (e.g. when the caller has already validated `input`), use
+    /// [new_from_input_unchecked](Sudoku::new_from_input_unchecked).
+    pub fn new_from_input(input: &[usize]) -> Result<Self, SudokuError> {
+        let nsq: usize = input.len();
+        let n: usize = (nsq as f64).sqrt().sqrt() as usize;
+
+        if nsq != n * n * n * n {
+            return Err(SudokuError::InvalidLength(nsq));
+        }
+        let nn = n * n;
+
+        for (i, &value) in input.iter().enumerate() {
+            if value > nn {
+                let row = i / nn;
+                let col = i - nn * row;
+                return Err(SudokuError::InvalidValue {
+                    row: row + 1,
+                    col: col + 1,
+                    value,
+                });
+            }
+        }
+
+        let mut rows = vec![vec![false; nn + 1]; nn];
+        let mut cols = vec![vec![false; nn + 1]; nn];
+        let mut boxes = vec![vec![false; nn + 1]; nn];
+
+        for (i, &value) in input.iter().enumerate() {
+            if value == 0 {
+                continue;
+            }
+            let row = i / nn;
+            let col = i - nn * row;
+            let b = (col) / n + n * (row / n);
+
+            if rows[row][value] || cols[col][value] || boxes[b][value] {
+                return Err(SudokuError::DuplicateGiven {
+                    row: row + 1,
+                    col: col + 1,
+                    value,
+                });
+            }
+            rows[row][value] = true;
+            cols[col][value] = true;
+            boxes[b][value] = true;
+        }
+
+        Ok(Self::new_from_input_unchecked(input))
+    }
+
+    /// Like [new_from_input](Sudoku::new_from_input), but skips all
+    /// validation and panics on malformed input, as `new_from_input` used to
+    /// do. Useful for callers who have already validated `input` and want to
+    /// avoid paying for the checks again.
+    pub fn new_from_input_unchecked(input: &[usize]) -> Self {
         let inputv = input.to_vec();
         let nsq: usize = inputv.len();
         let n: usize = (nsq as f64).sqrt().sqrt() as usize;
@@ -143,6 +398,692 @@ impl Sudoku {
 
         s
     }
+
+    /// Initialises an appropriately sized Sudoku with all of the correct
+    /// constraints, and then restricts each cell to the subset of values
+    /// listed in `candidates`, via [constrain_item](crate::solver::Solver::constrain_item)
+    ///
+    /// `candidates[i]` gives the allowed values for cell `i` (row-major),
+    /// with the full `1..=N` range meaning "unrestricted". This generalises
+    /// [new_from_input](Sudoku::new_from_input), where a given cell is just
+    /// one whose candidate list has a single element, to puzzles carrying
+    /// forward arbitrary pencil marks.
+    ///
+    /// Validates that `candidates` has a valid length and that every listed
+    /// value falls in range, returning a [SudokuError] describing the first
+    /// problem found. A restriction that leaves no solution is not an error
+    /// here; inspect [why_stuck](Sudoku::why_stuck) after construction
+    /// instead.
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// // A 4x4 sudoku (n=2) where R1C1 has been pencilled down to the
+    /// // candidates 1 or 3, and every other cell is unrestricted
+    /// let mut candidates = vec![(1..=4).collect::<Vec<usize>>(); 16];
+    /// candidates[0] = vec![1, 3];
+    /// let mut s = Sudoku::new_from_candidates(&candidates).unwrap();
+    /// let solution = s.next().unwrap();
+    /// assert!(solution[0] == 1 || solution[0] == 3);
+    /// ```
+    pub fn new_from_candidates(candidates: &[Vec<usize>]) -> Result<Self, SudokuError> {
+        let nsq: usize = candidates.len();
+        let n: usize = (nsq as f64).sqrt().sqrt() as usize;
+
+        if nsq != n * n * n * n {
+            return Err(SudokuError::InvalidLength(nsq));
+        }
+        let nn = n * n;
+
+        for (i, values) in candidates.iter().enumerate() {
+            let row = i / nn;
+            let col = i - nn * row;
+            for &value in values {
+                if value == 0 || value > nn {
+                    return Err(SudokuError::InvalidValue {
+                        row: row + 1,
+                        col: col + 1,
+                        value,
+                    });
+                }
+            }
+        }
+
+        let mut s = Self::new(n);
+        s.input = vec![0; nsq];
+
+        for (i, values) in candidates.iter().enumerate() {
+            if values.len() == nn {
+                continue;
+            }
+            let row = i / nn;
+            let col = i - nn * row;
+            let cell_con = (col + 1) + row * nn;
+            let allowed: Vec<String> = values
+                .iter()
+                .map(|v| format!("R{}C{}#{}", row + 1, col + 1, v))
+                .collect();
+            let allowed_refs: Vec<&str> = allowed.iter().map(String::as_str).collect();
+            s.solver
+                .constrain_item(cell_con, &allowed_refs)
+                .map_err(SudokuError::ConstructionFailed)?;
+        }
+
+        Ok(s)
+    }
+
+    /// Parses a jigsaw Sudoku from an ASCII-art grid where heavier borders
+    /// mark region boundaries, building a [new_with_regions](Sudoku::new_with_regions)
+    /// solver from the detected regions
+    ///
+    /// The expected format, for an edge length `N = n*n`: `N` cell rows
+    /// interleaved with `N - 1` border rows, `2*N - 1` lines in total.
+    /// Each cell row is `2*N - 1` characters: cell values (a digit/letter
+    /// in `0..=N`, with `.` or `0` meaning blank) at even positions, and a
+    /// vertical-border marker (`|` for a region boundary, any other
+    /// character for none) at odd positions. Each border row is also
+    /// `2*N - 1` characters, with a horizontal-border marker (`-` for a
+    /// region boundary, anything else for none) at even positions; odd
+    /// positions are ignored. For example, a 4x4 grid whose borders happen
+    /// to redraw the standard 2x2 boxes as explicit regions:
+    /// ```text
+    /// 1 2|3 4
+    /// . . . .
+    /// 3 4|1 2
+    /// - - - -
+    /// 2 1|4 3
+    /// . . . .
+    /// 4 3|2 1
+    /// ```
+    /// Regions are detected by flood-filling cells that aren't separated
+    /// by a border, and are validated to partition the grid into exactly
+    /// `N` regions of `N` cells each; the borders don't have to follow the
+    /// standard boxes, any partition into `N` same-sized connected regions
+    /// works.
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// let grid = "\
+    /// 1 2|3 4
+    /// . . . .
+    /// 3 4|1 2
+    /// - - - -
+    /// 2 1|4 3
+    /// . . . .
+    /// 4 3|2 1";
+    /// let mut s = Sudoku::parse_bordered(grid).unwrap();
+    /// let solution = s.next().unwrap();
+    /// assert_eq!(solution, vec![1, 2, 3, 4, 3, 4, 1, 2, 2, 1, 4, 3, 4, 3, 2, 1]);
+    /// ```
+    pub fn parse_bordered(text: &str) -> Result<Self, SudokuError> {
+        Self::parse_bordered_with_blank(text, '.')
+    }
+
+    /// Like [parse_bordered](Sudoku::parse_bordered), but recognizes `blank`
+    /// as an additional blank-cell marker instead of the hardcoded `.`
+    ///
+    /// `0` is always accepted as blank too, regardless of `blank`, since the
+    /// internal representation always uses `0` for an empty cell.
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// let grid = "\
+    /// 1 2|3 4
+    /// _ _ _ _
+    /// 3 4|1 2
+    /// - - - -
+    /// 2 1|4 3
+    /// _ _ _ _
+    /// 4 3|2 1";
+    /// let mut s = Sudoku::parse_bordered_with_blank(grid, '_').unwrap();
+    /// let solution = s.next().unwrap();
+    /// assert_eq!(solution, vec![1, 2, 3, 4, 3, 4, 1, 2, 2, 1, 4, 3, 4, 3, 2, 1]);
+    /// ```
+    pub fn parse_bordered_with_blank(text: &str, blank: char) -> Result<Self, SudokuError> {
+        let lines: Vec<&str> = text.lines().collect();
+        if lines.is_empty() || lines.len().is_multiple_of(2) {
+            return Err(SudokuError::MalformedGrid(format!(
+                "expected an odd number of lines, found {}",
+                lines.len()
+            )));
+        }
+        // `nn` is the grid's edge length N = n*n: `lines` interleaves `nn`
+        // cell rows with `nn - 1` border rows
+        let nn = lines.len().div_ceil(2);
+        let n = (nn as f64).sqrt() as usize;
+        if n * n != nn {
+            return Err(SudokuError::MalformedGrid(format!(
+                "grid edge length {nn} is not a perfect square"
+            )));
+        }
+        let width = 2 * nn - 1;
+
+        let cell_rows: Vec<&str> = lines.iter().step_by(2).copied().collect();
+        let border_rows: Vec<&str> = lines.iter().skip(1).step_by(2).copied().collect();
+
+        for (i, line) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(SudokuError::MalformedGrid(format!(
+                    "line {} has length {}, expected {width}",
+                    i + 1,
+                    line.chars().count()
+                )));
+            }
+        }
+
+        // Parse cell values, and the vertical (within-row) border markers
+        let mut grid = vec![0usize; nn * nn];
+        let mut right_wall = vec![vec![false; nn]; nn]; // right_wall[row][col]: border between col and col+1
+        for (row, line) in cell_rows.iter().enumerate() {
+            let chars: Vec<char> = line.chars().collect();
+            for col in 0..nn {
+                let c = chars[2 * col];
+                let value = match c {
+                    c if c == blank || c == '0' => 0,
+                    c => c
+                        .to_digit(36)
+                        .map(|d| d as usize)
+                        .ok_or_else(|| SudokuError::MalformedGrid(format!("invalid cell character '{c}'")))?,
+                };
+                if value > nn {
+                    return Err(SudokuError::InvalidValue {
+                        row: row + 1,
+                        col: col + 1,
+                        value,
+                    });
+                }
+                grid[row * nn + col] = value;
+                if col + 1 < nn {
+                    right_wall[row][col] = chars[2 * col + 1] == '|';
+                }
+            }
+        }
+
+        // Parse the horizontal (between-row) border markers
+        let mut bottom_wall = vec![vec![false; nn]; nn.saturating_sub(1)];
+        for (row, line) in border_rows.iter().enumerate() {
+            let chars: Vec<char> = line.chars().collect();
+            for col in 0..nn {
+                bottom_wall[row][col] = chars[2 * col] == '-';
+            }
+        }
+
+        // Union-find over the nn*nn cells, merging neighbours not
+        // separated by a wall
+        let mut parent: Vec<usize> = (0..nn * nn).collect();
+        fn find(parent: &mut [usize], x: usize) -> usize {
+            if parent[x] != x {
+                parent[x] = find(parent, parent[x]);
+            }
+            parent[x]
+        }
+        let union = |parent: &mut Vec<usize>, a: usize, b: usize| {
+            let ra = find(parent, a);
+            let rb = find(parent, b);
+            if ra != rb {
+                parent[ra] = rb;
+            }
+        };
+        for row in 0..nn {
+            for col in 0..nn {
+                let here = row * nn + col;
+                if col + 1 < nn && !right_wall[row][col] {
+                    union(&mut parent, here, here + 1);
+                }
+                if row + 1 < nn && !bottom_wall[row][col] {
+                    union(&mut parent, here, here + nn);
+                }
+            }
+        }
+
+        // Assign 0-indexed region ids in row-major order of first
+        // appearance, and validate the partition shape
+        let mut region_id: HashMap<usize, usize> = HashMap::new();
+        let mut regions = vec![0usize; nn * nn];
+        let mut region_sizes: Vec<usize> = vec![];
+        for (i, region) in regions.iter_mut().enumerate() {
+            let root = find(&mut parent, i);
+            let id = *region_id.entry(root).or_insert_with(|| {
+                region_sizes.push(0);
+                region_sizes.len() - 1
+            });
+            *region = id;
+            region_sizes[id] += 1;
+        }
+
+        if region_sizes.len() != nn {
+            return Err(SudokuError::InvalidRegionCount {
+                found: region_sizes.len(),
+                expected: nn,
+            });
+        }
+        for (region, &size) in region_sizes.iter().enumerate() {
+            if size != nn {
+                return Err(SudokuError::InvalidRegionSize {
+                    region,
+                    found: size,
+                    expected: nn,
+                });
+            }
+        }
+
+        let mut s = Self::new_with_regions(n, &regions);
+        s.input = grid.clone();
+        for (i, &value) in grid.iter().enumerate() {
+            if value != 0 {
+                let row = i / nn;
+                let col = i - nn * row;
+                let opt_string = format!("R{}C{}#{}", row + 1, col + 1, value);
+                s.solver
+                    .select(&opt_string)
+                    .map_err(|_| SudokuError::DuplicateGiven {
+                        row: row + 1,
+                        col: col + 1,
+                        value,
+                    })?;
+            }
+        }
+
+        Ok(s)
+    }
+
+    /// For puzzle research: finds minimal subsets of `grid`'s clues that
+    /// still leave the Sudoku with exactly one solution
+    ///
+    /// `grid` must already be a complete solution (no zeroes). Starting
+    /// from every cell as a clue, this greedily tries removing clues one
+    /// at a time -- using [new_from_input_unchecked](Sudoku::new_from_input_unchecked)
+    /// and [first_n_solutions](crate::solver::Solver::first_n_solutions)`(2)`
+    /// to check that exactly one solution remains -- until no more clue
+    /// can go without losing uniqueness. That "dig" is repeated with a
+    /// different removal order on each of up to `max_results` attempts to
+    /// surface distinct minimal clue sets, skipping any attempt that lands
+    /// on one already found. It only ever finds *a* minimal set (none of
+    /// its clues can be removed), not the global minimum across every
+    /// possible set, which for standard Sudoku is a hard open problem in
+    /// its own right (see the
+    /// [17-clue problem](https://en.wikipedia.org/wiki/Mathematics_of_Sudoku#Minimum_number_of_clues)).
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// let solved = vec![
+    ///     1, 2, 3, 4,
+    ///     3, 4, 1, 2,
+    ///     2, 1, 4, 3,
+    ///     4, 3, 2, 1,
+    /// ];
+    /// let clue_sets = Sudoku::minimal_clue_sets(&solved, 3);
+    /// assert!(!clue_sets.is_empty());
+    /// for clues in &clue_sets {
+    ///     let mut input = vec![0; solved.len()];
+    ///     for &(row, col) in clues {
+    ///         input[col + row * 4] = solved[col + row * 4];
+    ///     }
+    ///     let mut s = Sudoku::new_from_input(&input).unwrap();
+    ///     assert_eq!(s.next(), Some(solved.clone()));
+    ///     assert_eq!(s.next(), None);
+    /// }
+    /// ```
+    pub fn minimal_clue_sets(grid: &[usize], max_results: usize) -> Vec<Vec<(usize, usize)>> {
+        let nsq = grid.len();
+        if nsq == 0 {
+            return vec![];
+        }
+        let n = (nsq as f64).sqrt().sqrt() as usize;
+        let nn = n * n;
+
+        let mut found: Vec<Vec<(usize, usize)>> = Vec::new();
+
+        for attempt in 0..max_results {
+            let mut order: Vec<usize> = (0..nsq).collect();
+            order.rotate_left(attempt % nsq);
+
+            let mut kept = vec![true; nsq];
+            for &i in &order {
+                kept[i] = false;
+                let input: Vec<usize> = (0..nsq).map(|j| if kept[j] { grid[j] } else { 0 }).collect();
+                let mut candidate = Self::new_from_input_unchecked(&input);
+                let unique = candidate.solver.first_n_solutions(2).len() == 1;
+                if !unique {
+                    kept[i] = true;
+                }
+            }
+
+            let clue_set: Vec<(usize, usize)> = (0..nsq)
+                .filter(|&i| kept[i])
+                .map(|i| (i / nn, i % nn))
+                .collect();
+
+            if !found.contains(&clue_set) {
+                found.push(clue_set);
+            }
+        }
+
+        found
+    }
+
+    /// Exports this Sudoku's exact-cover formulation as a boolean
+    /// constraint matrix: for the standard 9x9 grid, 729 options
+    /// (`RxCy#z`) against 324 constraint items
+    ///
+    /// This is the textbook exact-cover encoding [new](Sudoku::new) builds
+    /// internally, exposed for teaching, for cross-validating against
+    /// other DLX implementations, or for feeding into an entirely
+    /// different solver. Round-trips through
+    /// [Solver::from_matrix](crate::solver::Solver::from_matrix) to
+    /// produce an equivalent (freshly-constructed, not-yet-iterated)
+    /// solver.
+    ///
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    ///# use dlx_rs::solver::Solver;
+    /// let s = Sudoku::new(2);
+    /// let (names, matrix) = s.to_matrix();
+    /// assert_eq!(names.len(), 4 * 4 * 4);
+    /// assert_eq!(matrix.len(), names.len());
+    /// assert_eq!(matrix[0].len(), 4 * 4 * 4);
+    ///
+    /// let rebuilt: Solver = Solver::from_matrix(&names, &matrix);
+    /// assert_eq!(rebuilt.count(), s.solver.clone().count());
+    /// ```
+    pub fn to_matrix(&self) -> (Vec<String>, Vec<Vec<bool>>) {
+        let description = self.solver.clone().into_problem_description();
+
+        let matrix = description
+            .options
+            .iter()
+            .map(|(_, items)| {
+                let mut row = vec![false; description.num_items];
+                for &(item, _) in items {
+                    row[item - 1] = true;
+                }
+                row
+            })
+            .collect();
+        let names = description.options.into_iter().map(|(name, _)| name).collect();
+
+        (names, matrix)
+    }
+
+    /// Returns every cell (other than `(row, col)` itself) that shares a
+    /// row, column, or region with it -- the cell's "peers", in the usual
+    /// Sudoku-solving sense of cells that can't hold the same value
+    ///
+    /// Purely geometric: this reads off [regions](Sudoku::regions) rather
+    /// than the solver's live constraint items, so it generalizes to a
+    /// jigsaw Sudoku's irregular regions for free, and the result doesn't
+    /// change as clues get applied or the search progresses. `row` and
+    /// `col` are 1-indexed, matching the rest of this type's API.
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// let s = Sudoku::new(3);
+    /// // Standard 9x9 Sudoku: 8 row peers + 8 column peers + 4 more from
+    /// // the box that aren't already in that row or column, 20 in total
+    /// assert_eq!(s.peer_cells(1, 1).len(), 20);
+    /// ```
+    pub fn peer_cells(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        let nn = self.side;
+        let region = self.regions[(row - 1) * nn + (col - 1)];
+
+        let mut peers = Vec::new();
+        for r in 1..=nn {
+            for c in 1..=nn {
+                if (r, c) == (row, col) {
+                    continue;
+                }
+                let same_region = self.regions[(r - 1) * nn + (c - 1)] == region;
+                if r == row || c == col || same_region {
+                    peers.push((r, c));
+                }
+            }
+        }
+        peers
+    }
+
+    /// Reports the peer cells that already hold `val`, i.e. the reasons a
+    /// tentative placement of `val` at `(row, col)` would conflict with the
+    /// current grid
+    ///
+    /// Computed directly from [peer_cells](Sudoku::peer_cells) and
+    /// `self.input`, independent of the solver's search state, so it works
+    /// just as well before a single move has been made. Pairs naturally
+    /// with [commit](Sudoku::commit): this reports *why* a placement would
+    /// be rejected, for an interactive UI to show the user as they type,
+    /// rather than rejecting it outright. `row` and `col` are 1-indexed.
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// let sudoku = vec![
+    ///     5, 3, 0, 0, 7, 0, 0, 0, 0,
+    ///     6, 0, 0, 1, 9, 5, 0, 0, 0,
+    ///     0, 9, 8, 0, 0, 0, 0, 6, 0,
+    ///     8, 0, 0, 0, 6, 0, 0, 0, 3,
+    ///     4, 0, 0, 8, 0, 3, 0, 0, 1,
+    ///     7, 0, 0, 0, 2, 0, 0, 0, 6,
+    ///     0, 6, 0, 0, 0, 0, 2, 8, 0,
+    ///     0, 0, 0, 4, 1, 9, 0, 0, 5,
+    ///     0, 0, 0, 0, 8, 0, 0, 7, 9,
+    /// ];
+    /// let s = Sudoku::new_from_input(&sudoku).unwrap();
+    /// // R1C3's row and column hold no 6, but it shares its box with
+    /// // R2C1's 6
+    /// assert_eq!(s.conflicts_for(1, 3, 6), vec![(2, 1)]);
+    /// // A value already absent from every peer has no conflicts at all
+    /// assert!(s.conflicts_for(1, 3, 4).is_empty());
+    /// ```
+    pub fn conflicts_for(&self, row: usize, col: usize, val: usize) -> Vec<(usize, usize)> {
+        let nn = self.side;
+        self.peer_cells(row, col)
+            .into_iter()
+            .filter(|&(r, c)| self.input[(c - 1) + nn * (r - 1)] == val)
+            .collect()
+    }
+
+    /// Reports human-readable descriptions of cells that, given the clues
+    /// applied so far, have no possible value remaining
+    ///
+    /// This inspects the "cell must contain a number" constraint items
+    /// directly: a cell is stuck if its item is still active (not yet
+    /// covered by a choice) but no option covers it any more. It turns an
+    /// unsolvable puzzle into actionable feedback rather than a silent empty
+    /// iterator.
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// // A consistent set of clues leaves no cell without a possible value
+    /// let mut sudoku = vec![0; 81];
+    /// sudoku[0] = 1; // R1C1 = 1
+    /// let s = Sudoku::new_from_input(&sudoku).unwrap();
+    /// assert!(s.why_stuck().is_empty());
+    /// ```
+    pub fn why_stuck(&self) -> Vec<String> {
+        let nn = self.side;
+        let mut stuck = vec![];
+
+        for row in 1..=nn {
+            for col in 1..=nn {
+                let cell_con = col + (row - 1) * nn;
+                if self.solver.is_item_active(cell_con) && self.solver.item_option_count(cell_con) == 0
+                {
+                    stuck.push(format!("R{}C{} has no possible value", row, col));
+                }
+            }
+        }
+
+        stuck
+    }
+
+    /// Finds and commits one forced cell -- a naked single (a cell with
+    /// only one remaining candidate) or a hidden single (a row, column or
+    /// box digit with only one cell left that can hold it) -- the way a
+    /// human solver fills in the obviously-forced cells of a puzzle
+    /// before having to guess
+    ///
+    /// Returns the placed `(row, col, value)` (1-indexed), or `None` if no
+    /// forced move exists. Repeatedly calling this until it returns `None`
+    /// solves "easy" puzzles outright and stalls on harder ones needing a
+    /// real guess, which is itself a difficulty signal; pair a stall with
+    /// [why_stuck](Sudoku::why_stuck) to tell "needs guessing" apart from
+    /// "an earlier clue left a cell with no legal value".
+    ///
+    /// Like [Solver::select](crate::solver::Solver::select), this commits
+    /// directly to the link structure rather than going through the
+    /// search stages, so it must be called before [next](Sudoku::next)
+    /// starts iterating.
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// // Every cell but R1C1 is filled in, so R1C1's row, column and box
+    /// // between them rule out every value except the correct one, 1
+    /// let sudoku = vec![
+    ///     0, 2, 3, 4,
+    ///     3, 4, 1, 2,
+    ///     2, 1, 4, 3,
+    ///     4, 3, 2, 1,
+    /// ];
+    /// let mut s = Sudoku::new_from_input(&sudoku).unwrap();
+    /// assert_eq!(s.solve_step(), Some((1, 1, 1)));
+    /// // The grid is now fully determined, so no forced move remains
+    /// assert_eq!(s.solve_step(), None);
+    /// ```
+    pub fn solve_step(&mut self) -> Option<(usize, usize, usize)> {
+        let nn = self.side;
+        let n2 = nn * nn;
+
+        // Naked single: a cell whose own "must hold a number" item has
+        // only one remaining candidate value
+        for row in 1..=nn {
+            for col in 1..=nn {
+                let cell_con = col + (row - 1) * nn;
+                if self.solver.is_item_active(cell_con) {
+                    let options = self.solver.options_for_item(cell_con);
+                    if options.len() == 1 {
+                        return self.commit(&options[0]);
+                    }
+                }
+            }
+        }
+
+        // Hidden single: a row/column/box "must contain this digit" item
+        // with only one cell left that can take it, even though that cell
+        // may still have other candidates of its own
+        for constraint in (n2 + 1)..=(4 * n2) {
+            if self.solver.is_item_active(constraint) {
+                let options = self.solver.options_for_item(constraint);
+                if options.len() == 1 {
+                    return self.commit(&options[0]);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Selects `option` (an `"R{row}C{col}#{value}"` name) and records the
+    /// placed value in `self.input`, matching how the givens passed to
+    /// [new_from_input](Sudoku::new_from_input) are stored
+    fn commit(&mut self, option: &str) -> Option<(usize, usize, usize)> {
+        self.solver.select(option).ok()?;
+
+        let parts: Vec<&str> = option.split(&['R', 'C', '#']).collect();
+        let row: usize = parts[1].parse().ok()?;
+        let col: usize = parts[2].parse().ok()?;
+        let value: usize = parts[3].parse().ok()?;
+
+        let nn = self.side;
+        self.input[(col - 1) + nn * (row - 1)] = value;
+        Some((row, col, value))
+    }
+
+    /// Like [next](Sudoku::next), but reshapes the flat `Vec<usize>` into
+    /// `N` rows of `N` cells each, saving callers from hand-rolling the
+    /// error-prone `i * N + j` indexing themselves
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// let sudoku = vec![
+    ///     5, 3, 0, 0, 7, 0, 0, 0, 0,
+    ///     6, 0, 0, 1, 9, 5, 0, 0, 0,
+    ///     0, 9, 8, 0, 0, 0, 0, 6, 0,
+    ///     8, 0, 0, 0, 6, 0, 0, 0, 3,
+    ///     4, 0, 0, 8, 0, 3, 0, 0, 1,
+    ///     7, 0, 0, 0, 2, 0, 0, 0, 6,
+    ///     0, 6, 0, 0, 0, 0, 2, 8, 0,
+    ///     0, 0, 0, 4, 1, 9, 0, 0, 5,
+    ///     0, 0, 0, 0, 8, 0, 0, 7, 9,
+    /// ];
+    /// let mut s = Sudoku::new_from_input(&sudoku).unwrap();
+    ///
+    /// let grid = s.next_2d().unwrap();
+    /// assert_eq!(grid.len(), 9);
+    /// assert_eq!(grid[0], vec![5, 3, 4, 6, 7, 8, 9, 1, 2]);
+    /// ```
+    pub fn next_2d(&mut self) -> Option<Vec<Vec<usize>>> {
+        let nn = self.side;
+        let flat = self.next()?;
+        Some(flat.chunks(nn).map(|row| row.to_vec()).collect())
+    }
+
+    /// Solves the puzzle and returns its unique solution, erroring if it
+    /// has zero or more than one
+    ///
+    /// Combines [count_up_to_parallel](crate::solver::Solver::count_up_to_parallel)`(2)`
+    /// -- the same bounded check [check_bank] uses, which stops as soon as
+    /// a second solution rules out uniqueness -- with decoding the
+    /// solution via [next](Sudoku::next). The operation a "validate and
+    /// solve this puzzle" endpoint wants in one call, rather than checking
+    /// uniqueness and then re-solving separately.
+    /// ```
+    ///# use dlx_rs::sudoku::{Sudoku, SudokuError};
+    /// let unique = vec![
+    ///     5, 3, 0, 0, 7, 0, 0, 0, 0,
+    ///     6, 0, 0, 1, 9, 5, 0, 0, 0,
+    ///     0, 9, 8, 0, 0, 0, 0, 6, 0,
+    ///     8, 0, 0, 0, 6, 0, 0, 0, 3,
+    ///     4, 0, 0, 8, 0, 3, 0, 0, 1,
+    ///     7, 0, 0, 0, 2, 0, 0, 0, 6,
+    ///     0, 6, 0, 0, 0, 0, 2, 8, 0,
+    ///     0, 0, 0, 4, 1, 9, 0, 0, 5,
+    ///     0, 0, 0, 0, 8, 0, 0, 7, 9,
+    /// ];
+    /// let mut s = Sudoku::new_from_input(&unique).unwrap();
+    /// assert!(s.solve_unique().is_ok());
+    ///
+    /// let mut blank = Sudoku::new_from_input(&vec![0; 81]).unwrap();
+    /// assert_eq!(blank.solve_unique(), Err(SudokuError::MultipleSolutions));
+    ///
+    /// // Row 1 repeats the clue 1, conflicting in its box -- unsolvable
+    /// let mut contradictory = vec![1, 1, 0, 0, 0, 0, 0, 0, 0];
+    /// contradictory.extend(std::iter::repeat_n(0, 72));
+    /// let mut impossible = Sudoku::new_from_input_unchecked(&contradictory);
+    /// assert_eq!(impossible.solve_unique(), Err(SudokuError::NoSolution));
+    /// ```
+    pub fn solve_unique(&mut self) -> Result<Vec<usize>, SudokuError> {
+        match self.solver.count_up_to_parallel(2) {
+            0 => Err(SudokuError::NoSolution),
+            1 => Ok(self
+                .next()
+                .expect("count_up_to_parallel(2) reported exactly one solution")),
+            _ => Err(SudokuError::MultipleSolutions),
+        }
+    }
+
+    /// Exhausts the search and serializes every remaining solution as a
+    /// JSON array of `N`x`N` grids (an array of rows, each an array of
+    /// cell values), rather than [next](Sudoku::next)'s flat form
+    ///
+    /// Eager, like [Solver::solutions_json](crate::solver::Solver::solutions_json):
+    /// the full solution set is collected before being serialized.
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// let sudoku = vec![0; 16];
+    /// let mut s = Sudoku::new_from_input(&sudoku).unwrap();
+    ///
+    /// let json = s.solutions_json();
+    /// let grids: Vec<Vec<Vec<usize>>> = serde_json::from_str(&json).unwrap();
+    /// assert_eq!(grids[0].len(), 4);
+    /// assert_eq!(grids[0][0].len(), 4);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn solutions_json(&mut self) -> String {
+        let nn = self.side;
+        let grids: Vec<Vec<Vec<usize>>> = self
+            .by_ref()
+            .map(|flat| flat.chunks(nn).map(|row| row.to_vec()).collect())
+            .collect();
+        serde_json::to_string(&grids).expect("Vec<Vec<Vec<usize>>> always serializes")
+    }
 }
 
 impl Iterator for Sudoku {
@@ -166,7 +1107,7 @@ impl Iterator for Sudoku {
     /// ];
     ///
     /// // Create new sudoku from this grid
-    /// let mut s = Sudoku::new_from_input(&sudoku);
+    /// let mut s = Sudoku::new_from_input(&sudoku).unwrap();
     ///
     /// let true_solution = vec![
     ///     5, 3, 4, 6, 7, 8, 9, 1, 2,
@@ -197,7 +1138,7 @@ impl Iterator for Sudoku {
                 let r: usize = s[1].parse().unwrap();
                 let c: usize = s[2].parse().unwrap();
                 let v: usize = s[3].parse().unwrap();
-                sudoku_solved[(c - 1) + self.n * self.n * (r - 1)] = v;
+                sudoku_solved[(c - 1) + self.side * (r - 1)] = v;
             }
             Some(sudoku_solved)
         } else {
@@ -206,6 +1147,9 @@ impl Iterator for Sudoku {
     }
 }
 
+/// `next` forwards directly to the underlying [Solver], which is fused
+impl std::iter::FusedIterator for Sudoku {}
+
 impl Sudoku {
     /// Takes an input sudoku array and produces a pretty printed version
     /// ```
@@ -240,6 +1184,19 @@ impl Sudoku {
     ///
     ///
     pub fn pretty(sudoku_solved: &[usize]) -> String {
+        Self::pretty_with_blank(sudoku_solved, ' ')
+    }
+
+    /// Like [pretty](Sudoku::pretty), but prints `blank` in place of an
+    /// empty (`0`) cell instead of the hardcoded space
+    /// ```
+    ///# use dlx_rs::sudoku::Sudoku;
+    /// let sudoku = vec![0; 81];
+    /// let pretty = Sudoku::pretty_with_blank(&sudoku, '_');
+    /// assert!(pretty.contains('_'));
+    /// assert!(!pretty.contains('0'));
+    /// ```
+    pub fn pretty_with_blank(sudoku_solved: &[usize], blank: char) -> String {
         let mut result = String::new();
         let n = (sudoku_solved.len() as f64).sqrt().sqrt() as usize;
         #[allow(non_snake_case)]
@@ -249,7 +1206,7 @@ impl Sudoku {
             result += " ";
             for j in 0..N {
                 result += &match sudoku_solved[i * N + j] {
-                    0 => String::from(" "),
+                    0 => blank.to_string(),
                     v => v.to_string(),
                 };
                 result += " ";
@@ -274,6 +1231,73 @@ impl Sudoku {
     }
 }
 
+/// How many solutions a puzzle passed to [check_bank] turned out to have
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Uniqueness {
+    /// The puzzle (including one whose givens directly conflict) has no
+    /// solution at all
+    NoSolution,
+    /// The puzzle has exactly one solution
+    Unique,
+    /// The puzzle has more than one solution
+    Multiple,
+}
+
+/// Classifies every puzzle in `puzzles` by how many solutions it has,
+/// across all of them in parallel
+///
+/// Each puzzle is solved independently with
+/// [count_up_to_parallel](crate::solver::Solver::count_up_to_parallel)`(2)`,
+/// which stops as soon as a second solution rules out uniqueness -- the
+/// bulk operation a puzzle bank's maintainer needs to sanity-check a whole
+/// collection at once, rather than checking each puzzle by hand. A puzzle
+/// whose givens directly conflict (see [SudokuError::DuplicateGiven])
+/// is classified as [NoSolution](Uniqueness::NoSolution) along with any
+/// puzzle that parses fine but has no valid completion.
+/// ```
+///# use dlx_rs::sudoku::{check_bank, Uniqueness};
+/// let unique = vec![
+///     5, 3, 0, 0, 7, 0, 0, 0, 0,
+///     6, 0, 0, 1, 9, 5, 0, 0, 0,
+///     0, 9, 8, 0, 0, 0, 0, 6, 0,
+///     8, 0, 0, 0, 6, 0, 0, 0, 3,
+///     4, 0, 0, 8, 0, 3, 0, 0, 1,
+///     7, 0, 0, 0, 2, 0, 0, 0, 6,
+///     0, 6, 0, 0, 0, 0, 2, 8, 0,
+///     0, 0, 0, 4, 1, 9, 0, 0, 5,
+///     0, 0, 0, 0, 8, 0, 0, 7, 9,
+/// ];
+/// let ambiguous = vec![0; 81];
+/// let contradictory = vec![1, 1, 0, 0, 0, 0, 0, 0, 0, /* row 1 repeats a clue, conflicting in its box */
+///     0, 0, 0, 0, 0, 0, 0, 0, 0,
+///     0, 0, 0, 0, 0, 0, 0, 0, 0,
+///     0, 0, 0, 0, 0, 0, 0, 0, 0,
+///     0, 0, 0, 0, 0, 0, 0, 0, 0,
+///     0, 0, 0, 0, 0, 0, 0, 0, 0,
+///     0, 0, 0, 0, 0, 0, 0, 0, 0,
+///     0, 0, 0, 0, 0, 0, 0, 0, 0,
+///     0, 0, 0, 0, 0, 0, 0, 0, 0,
+/// ];
+///
+/// assert_eq!(
+///     check_bank(&[unique, ambiguous, contradictory]),
+///     vec![Uniqueness::Unique, Uniqueness::Multiple, Uniqueness::NoSolution]
+/// );
+/// ```
+pub fn check_bank(puzzles: &[Vec<usize>]) -> Vec<Uniqueness> {
+    puzzles
+        .par_iter()
+        .map(|puzzle| match Sudoku::new_from_input(puzzle) {
+            Err(_) => Uniqueness::NoSolution,
+            Ok(sudoku) => match sudoku.solver.count_up_to_parallel(2) {
+                0 => Uniqueness::NoSolution,
+                1 => Uniqueness::Unique,
+                _ => Uniqueness::Multiple,
+            },
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -286,7 +1310,7 @@ mod test {
             0, 0, 2, 8, 0, 0, 0, 0, 4, 1, 9, 0, 0, 5, 0, 0, 0, 0, 8, 0, 0, 7, 9,
         ];
 
-        let mut s = Sudoku::new_from_input(&sudoku);
+        let mut s = Sudoku::new_from_input(&sudoku).unwrap();
 
         let true_solution = vec![
             5, 3, 4, 6, 7, 8, 9, 1, 2, 6, 7, 2, 1, 9, 5, 3, 4, 8, 1, 9, 8, 3, 4, 2, 5, 6, 7, 8, 5,
@@ -296,4 +1320,276 @@ mod test {
         let sol = s.next().unwrap();
         assert_eq!(sol, true_solution);
     }
+
+    #[test]
+    fn solve_unique_reports_all_three_outcomes() {
+        let sudoku = vec![
+            5, 3, 0, 0, 7, 0, 0, 0, 0, 6, 0, 0, 1, 9, 5, 0, 0, 0, 0, 9, 8, 0, 0, 0, 0, 6, 0, 8, 0,
+            0, 0, 6, 0, 0, 0, 3, 4, 0, 0, 8, 0, 3, 0, 0, 1, 7, 0, 0, 0, 2, 0, 0, 0, 6, 0, 6, 0, 0,
+            0, 0, 2, 8, 0, 0, 0, 0, 4, 1, 9, 0, 0, 5, 0, 0, 0, 0, 8, 0, 0, 7, 9,
+        ];
+        let true_solution = vec![
+            5, 3, 4, 6, 7, 8, 9, 1, 2, 6, 7, 2, 1, 9, 5, 3, 4, 8, 1, 9, 8, 3, 4, 2, 5, 6, 7, 8, 5,
+            9, 7, 6, 1, 4, 2, 3, 4, 2, 6, 8, 5, 3, 7, 9, 1, 7, 1, 3, 9, 2, 4, 8, 5, 6, 9, 6, 1, 5,
+            3, 7, 2, 8, 4, 2, 8, 7, 4, 1, 9, 6, 3, 5, 3, 4, 5, 2, 8, 6, 1, 7, 9,
+        ];
+        let mut unique = Sudoku::new_from_input(&sudoku).unwrap();
+        assert_eq!(unique.solve_unique(), Ok(true_solution));
+
+        let mut blank = Sudoku::new_from_input(&vec![0; 81]).unwrap();
+        assert_eq!(blank.solve_unique(), Err(SudokuError::MultipleSolutions));
+
+        let mut contradictory_input = vec![1, 1, 0, 0, 0, 0, 0, 0, 0];
+        contradictory_input.extend(std::iter::repeat_n(0, 72));
+        let mut contradictory = Sudoku::new_from_input_unchecked(&contradictory_input);
+        assert_eq!(contradictory.solve_unique(), Err(SudokuError::NoSolution));
+    }
+
+    #[test]
+    fn to_matrix_round_trips_through_from_matrix() {
+        let s = Sudoku::new(2);
+        let (names, matrix) = s.to_matrix();
+
+        assert_eq!(names.len(), matrix.len());
+        for row in &matrix {
+            assert_eq!(row.len(), matrix.len());
+        }
+
+        let rebuilt: Solver = Solver::from_matrix(&names, &matrix);
+        assert_eq!(rebuilt.count(), s.solver.clone().count());
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows usize")]
+    fn new_panics_rather_than_overflow() {
+        Sudoku::new(usize::MAX);
+    }
+
+    #[test]
+    fn new_at_ordinary_size_does_not_panic() {
+        // Far below any overflow boundary, so this should construct as normal
+        let _ = Sudoku::new(3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn solutions_json_round_trips_as_grids() {
+        let sudoku = vec![0; 16];
+        let mut s = Sudoku::new_from_input(&sudoku).unwrap();
+
+        let json = s.solutions_json();
+        let grids: Vec<Vec<Vec<usize>>> = serde_json::from_str(&json).unwrap();
+
+        let mut expected = Sudoku::new_from_input(&sudoku).unwrap();
+        let flat = expected.next().unwrap();
+        assert_eq!(grids[0], flat.chunks(4).map(|row| row.to_vec()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn parse_bordered_solves_box_shaped_jigsaw() {
+        let grid = "\
+1 2|3 4
+. . . .
+3 4|1 2
+- - - -
+2 1|4 3
+. . . .
+4 3|2 1";
+        let mut s = Sudoku::parse_bordered(grid).unwrap();
+        assert_eq!(s.next(), Some(vec![1, 2, 3, 4, 3, 4, 1, 2, 2, 1, 4, 3, 4, 3, 2, 1]));
+        assert_eq!(s.next(), None);
+    }
+
+    #[test]
+    fn parse_bordered_with_blank_accepts_a_custom_marker() {
+        let grid = "\
+1 2|3 4
+_ _ _ _
+3 4|1 2
+- - - -
+2 1|4 3
+_ _ _ _
+4 3|2 1";
+        let mut s = Sudoku::parse_bordered_with_blank(grid, '_').unwrap();
+        assert_eq!(s.next(), Some(vec![1, 2, 3, 4, 3, 4, 1, 2, 2, 1, 4, 3, 4, 3, 2, 1]));
+    }
+
+    #[test]
+    fn pretty_with_blank_uses_the_given_marker() {
+        let sudoku = vec![0; 16];
+        let pretty = Sudoku::pretty_with_blank(&sudoku, '_');
+        assert!(pretty.contains('_'));
+        assert!(!pretty.contains('0'));
+    }
+
+    #[test]
+    fn parse_bordered_rejects_wrong_line_count() {
+        let grid = "1 2|3 4\n3 4|1 2";
+        assert!(matches!(
+            Sudoku::parse_bordered(grid),
+            Err(SudokuError::MalformedGrid(_))
+        ));
+    }
+
+    #[test]
+    fn parse_bordered_rejects_region_that_is_too_large() {
+        // No walls at all: one 16-cell region instead of four 4-cell ones
+        let grid = "\
+1 2 3 4
+. . . .
+3 4 1 2
+. . . .
+2 1 4 3
+. . . .
+4 3 2 1";
+        assert!(matches!(
+            Sudoku::parse_bordered(grid),
+            Err(SudokuError::InvalidRegionCount {
+                found: 1,
+                expected: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn construction_failed_exposes_the_inner_solver_error_via_source() {
+        use std::error::Error;
+
+        let err = SudokuError::ConstructionFailed(crate::solver::SolverError::UnknownOption(
+            "R1C1#9".to_string(),
+        ));
+        let source = err
+            .source()
+            .expect("ConstructionFailed should expose its inner SolverError");
+        assert_eq!(source.to_string(), "no option named \"R1C1#9\"");
+    }
+
+    #[test]
+    fn item_degree_histogram_is_uniform_for_a_fresh_9x9_board() {
+        // Every row/column/box/cell constraint for a 9x9 board starts out
+        // covered by exactly 9 options (one per candidate digit), so the
+        // histogram has a single entry
+        let s = Sudoku::new(3);
+        assert_eq!(
+            s.solver.item_degree_histogram(),
+            std::collections::BTreeMap::from([(9, 9 * 9 * 4)])
+        );
+    }
+
+    #[test]
+    fn solve_step_solves_an_almost_complete_grid_cell_by_cell() {
+        let solved = vec![
+            1, 2, 3, 4, 3, 4, 1, 2, 2, 1, 4, 3, 4, 3, 2, 1,
+        ];
+        let mut sudoku = solved.clone();
+        sudoku[0] = 0; // blank R1C1, forced back to 1 by its row/col/box
+        let mut s = Sudoku::new_from_input(&sudoku).unwrap();
+
+        assert_eq!(s.solve_step(), Some((1, 1, 1)));
+        assert_eq!(s.solve_step(), None);
+        assert_eq!(s.next(), Some(solved));
+    }
+
+    #[test]
+    fn solve_step_stalls_on_a_blank_grid() {
+        // No clues at all means no cell or digit is forced anywhere
+        let mut s = Sudoku::new_from_input(&[0; 16]).unwrap();
+        assert_eq!(s.solve_step(), None);
+        assert!(s.why_stuck().is_empty());
+    }
+
+    #[test]
+    fn conflicts_for_finds_a_row_conflict() {
+        let mut sudoku = vec![0; 16];
+        sudoku[1] = 2; // R1C2 = 2
+        let s = Sudoku::new_from_input(&sudoku).unwrap();
+        assert_eq!(s.conflicts_for(1, 4, 2), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn conflicts_for_finds_a_column_conflict() {
+        let mut sudoku = vec![0; 16];
+        sudoku[9] = 3; // R3C2 = 3
+        let s = Sudoku::new_from_input(&sudoku).unwrap();
+        assert_eq!(s.conflicts_for(1, 2, 3), vec![(3, 2)]);
+    }
+
+    #[test]
+    fn conflicts_for_finds_a_box_conflict() {
+        let mut sudoku = vec![0; 16];
+        sudoku[5] = 4; // R2C2 = 4, same box as R1C1
+        let s = Sudoku::new_from_input(&sudoku).unwrap();
+        assert_eq!(s.conflicts_for(1, 1, 4), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn conflicts_for_is_empty_when_no_peer_holds_the_value() {
+        let s = Sudoku::new_from_input(&[0; 16]).unwrap();
+        assert!(s.conflicts_for(1, 1, 1).is_empty());
+    }
+
+    #[test]
+    fn estimated_difficulty_distinguishes_blank_from_given_sudoku() {
+        use crate::solver::DifficultyClass;
+
+        let blank = Sudoku::new(3);
+        assert_eq!(blank.solver.estimated_difficulty(), DifficultyClass::LikelyIntractable);
+
+        let given = vec![
+            5, 3, 0, 0, 7, 0, 0, 0, 0, 6, 0, 0, 1, 9, 5, 0, 0, 0, 0, 9, 8, 0, 0, 0, 0, 6, 0, 8, 0,
+            0, 0, 6, 0, 0, 0, 3, 4, 0, 0, 8, 0, 3, 0, 0, 1, 7, 0, 0, 0, 2, 0, 0, 0, 6, 0, 6, 0, 0,
+            0, 0, 2, 8, 0, 0, 0, 0, 4, 1, 9, 0, 0, 5, 0, 0, 0, 0, 8, 0, 0, 7, 9,
+        ];
+        let s = Sudoku::new_from_input(&given).unwrap();
+        assert_eq!(s.solver.estimated_difficulty(), DifficultyClass::Moderate);
+    }
+
+    #[test]
+    fn check_bank_classifies_a_mixed_puzzle_bank() {
+        // A fully-solved 4x4 grid with one blank: forced back by its row,
+        // column, and box, so exactly one solution
+        let unique = vec![0, 2, 3, 4, 3, 4, 1, 2, 2, 1, 4, 3, 4, 3, 2, 1];
+        // No clues at all: plenty of valid completions
+        let ambiguous = vec![0; 16];
+        // R1C1 and R1C2 are both given as "1", conflicting in the same row
+        let contradictory = vec![1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+        assert_eq!(
+            check_bank(&[unique, ambiguous, contradictory]),
+            vec![Uniqueness::Unique, Uniqueness::Multiple, Uniqueness::NoSolution]
+        );
+    }
+
+    #[test]
+    fn new_rect_solves_a_known_6x6_puzzle_of_2x3_boxes() {
+        let puzzle = vec![
+            1, 2, 3, 4, 5, 6, 4, 5, 6, 1, 2, 3, 2, 3, 1, 5, 6, 4, 5, 6, 4, 2, 3, 1, 3, 1, 2, 6, 4,
+            5, 6, 4, 5, 3, 1, 2,
+        ];
+        let mut given = vec![0; 36];
+        for row in 0..6 {
+            given[row * 6] = puzzle[row * 6]; // first column
+        }
+        given[..6].copy_from_slice(&puzzle[..6]); // first row
+
+        let mut s = Sudoku::new_rect(2, 3);
+        s.input = given.clone();
+        for (i, &value) in given.iter().enumerate() {
+            if value != 0 {
+                let row = i / 6;
+                let col = i - 6 * row;
+                s.solver.select(&format!("R{}C{}#{}", row + 1, col + 1, value)).unwrap();
+            }
+        }
+
+        assert_eq!(s.next(), Some(puzzle));
+    }
+
+    #[test]
+    fn new_rect_grid_side_is_the_product_of_the_box_dimensions() {
+        let s = Sudoku::new_rect(3, 4);
+        assert_eq!(s.to_matrix().0.len(), 12 * 12 * 12);
+        assert_eq!(s.regions.len(), 12 * 12);
+        assert_eq!(*s.regions.iter().max().unwrap(), 12 - 1);
+    }
 }
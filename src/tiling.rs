@@ -0,0 +1,178 @@
+use crate::Solver;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// The cells covered by a single piece placement, as `(row, col)` pairs
+type Cells = Vec<(usize, usize)>;
+
+/// A polyomino piece, described by the `(row, col)` offsets of its cells
+/// relative to an arbitrary reference cell
+#[derive(Clone, Debug)]
+pub struct Polyomino {
+    cells: Vec<(isize, isize)>,
+}
+
+impl Polyomino {
+    /// Creates a new polyomino from a set of relative cell offsets
+    ///
+    /// ```
+    ///# use dlx_rs::tiling::Polyomino;
+    /// // A horizontal domino
+    /// let domino = Polyomino::new(vec![(0, 0), (0, 1)]);
+    /// ```
+    pub fn new(cells: Vec<(isize, isize)>) -> Self {
+        Polyomino { cells }
+    }
+
+    /// Every distinct orientation of this piece under the 4 rotations and
+    /// their reflections, each normalised so its minimum row and column
+    /// offset is zero, with duplicates (from symmetric pieces) removed
+    fn orientations(&self) -> Vec<Vec<(isize, isize)>> {
+        let mut seen = HashSet::new();
+        let mut orientations = Vec::new();
+
+        let mut current = self.cells.clone();
+        for _ in 0..4 {
+            for flip in [false, true] {
+                let variant: Vec<(isize, isize)> = if flip {
+                    current.iter().map(|&(r, c)| (r, -c)).collect()
+                } else {
+                    current.clone()
+                };
+                let normalised = Self::normalise(&variant);
+                if seen.insert(normalised.clone()) {
+                    orientations.push(normalised);
+                }
+            }
+            current = current.iter().map(|&(r, c)| (c, -r)).collect();
+        }
+
+        orientations
+    }
+
+    fn normalise(cells: &[(isize, isize)]) -> Vec<(isize, isize)> {
+        let min_r = cells.iter().map(|&(r, _)| r).min().unwrap_or(0);
+        let min_c = cells.iter().map(|&(_, c)| c).min().unwrap_or(0);
+        let mut normalised: Vec<(isize, isize)> =
+            cells.iter().map(|&(r, c)| (r - min_r, c - min_c)).collect();
+        normalised.sort_unstable();
+        normalised
+    }
+}
+
+/// Tiles a grid (with optional holes) using copies of a set of polyomino
+/// pieces, trying every orientation (rotations and reflections) at every
+/// position
+///
+/// Each coverable cell becomes a mandatory item, and each legal placement
+/// of a piece (in a given orientation, at a given position) becomes an
+/// option tagged with `(piece_id, cells)` metadata, where `piece_id` is
+/// the index of the piece within the slice passed to [new](Tiling::new).
+/// This generalises the domino tiling used by [Aztec](crate::aztec::Aztec)
+/// to arbitrary pieces and arbitrary (possibly non-rectangular) boards.
+///
+/// ```
+///# use dlx_rs::tiling::{Polyomino, Tiling};
+/// // A 2x2 board tiled with dominoes: either two horizontal or two
+/// // vertical placements work, giving exactly 2 tilings
+/// let grid = vec![vec![true, true], vec![true, true]];
+/// let domino = Polyomino::new(vec![(0, 0), (0, 1)]);
+/// let tiling = Tiling::new(&grid, &[domino]);
+/// assert_eq!(tiling.count(), 2);
+/// ```
+pub struct Tiling {
+    rows: usize,
+    cols: usize,
+    solver: Solver<(usize, Cells)>,
+}
+
+impl Tiling {
+    /// Builds a tiling problem over `grid` (row-major, `true` marking a
+    /// coverable cell and `false` a hole) using copies of `pieces`,
+    /// identified by their position in the slice
+    pub fn new(grid: &[Vec<bool>], pieces: &[Polyomino]) -> Tiling {
+        let rows = grid.len();
+        let cols = grid.first().map_or(0, |row| row.len());
+
+        // Assign an item number to every coverable cell, scanning row-major
+        let mut item_of = HashMap::new();
+        let mut n = 0;
+        for (r, row) in grid.iter().enumerate() {
+            for (c, &coverable) in row.iter().enumerate() {
+                if coverable {
+                    n += 1;
+                    item_of.insert((r, c), n);
+                }
+            }
+        }
+
+        let mut solver: Solver<(usize, Cells)> = Solver::new(n);
+
+        for (piece_id, piece) in pieces.iter().enumerate() {
+            for orientation in piece.orientations() {
+                for base_r in 0..rows {
+                    for base_c in 0..cols {
+                        if let Some((items, placement)) =
+                            Self::place(&item_of, &orientation, base_r, base_c)
+                        {
+                            let name = format!("p{piece_id}@{base_r},{base_c}");
+                            solver.add_option_with_meta(&name, &items, (piece_id, placement));
+                        }
+                    }
+                }
+            }
+        }
+
+        Tiling { rows, cols, solver }
+    }
+
+    /// Returns the `(items, cells)` covered by placing `orientation` with
+    /// its reference cell at `(base_r, base_c)`, or `None` if any cell
+    /// falls outside the grid or onto a hole
+    fn place(
+        item_of: &HashMap<(usize, usize), usize>,
+        orientation: &[(isize, isize)],
+        base_r: usize,
+        base_c: usize,
+    ) -> Option<(Vec<usize>, Cells)> {
+        let mut items = Vec::with_capacity(orientation.len());
+        let mut cells = Vec::with_capacity(orientation.len());
+
+        for &(dr, dc) in orientation {
+            let r = base_r as isize + dr;
+            let c = base_c as isize + dc;
+            if r < 0 || c < 0 {
+                return None;
+            }
+            let &item = item_of.get(&(r as usize, c as usize))?;
+            items.push(item);
+            cells.push((r as usize, c as usize));
+        }
+
+        Some((items, cells))
+    }
+
+    /// Returns the `(rows, cols)` dimensions of the board being tiled
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+}
+
+impl Iterator for Tiling {
+    type Item = Vec<(usize, Cells)>;
+    /// Returns the next tiling, as a vector of `(piece_id, cells)`
+    /// placements
+    fn next(&mut self) -> Option<Self::Item> {
+        self.solver.next()?;
+        Some(
+            self.solver
+                .output_meta()
+                .into_iter()
+                .map(|m| m.expect("every tiling option carries metadata").clone())
+                .collect(),
+        )
+    }
+}
+
+/// `next` forwards directly to the underlying [Solver], which is fused
+impl std::iter::FusedIterator for Tiling {}